@@ -0,0 +1,254 @@
+// commands/data_dir.rs
+// Moves the app's data (settings, notes, UI state, logs) to another directory,
+// e.g. on another drive. The new location is recorded in DATA_DIR_POINTER in
+// the default data dir, so it sticks across restarts.
+
+use crate::commands::logs::{active_log_file, LOG_FILE_NAME};
+use crate::state::settings::{
+    get_app_dir, get_default_app_dir, save_settings, write_atomic, DATA_DIR_POINTER, SETTINGS_FILE,
+};
+use crate::state::settings_saver::SettingsSaver;
+use crate::types::WhisperSettings;
+use log::{info, warn};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+
+const DATA_FILES: [&str; 3] = [SETTINGS_FILE, "notes.json", "ui_state.json"];
+
+// ============================================================================
+// Relocate Data Dir Command
+// ============================================================================
+// Copies everything first and only removes the old files once the copy and
+// the pointer update succeeded; on failure the new directory is cleaned up
+// and the old one is left untouched. Logs move on the next start.
+#[tauri::command]
+pub fn relocate_data_dir_command(
+    app_handle: AppHandle,
+    state: State<Mutex<Option<WhisperSettings>>>,
+    new_path: String,
+) -> Result<(), String> {
+    let new_dir = PathBuf::from(new_path.trim());
+    if !new_dir.is_absolute() {
+        return Err("The data directory must be an absolute path".to_string());
+    }
+
+    let default_dir = get_default_app_dir(&app_handle)?;
+    let current_dir = get_app_dir(&app_handle)?;
+    if same_dir(&new_dir, &current_dir) {
+        return Err("That is already the data directory".to_string());
+    }
+
+    let mut moves: Vec<FileMove> = DATA_FILES
+        .iter()
+        .map(|name| FileMove {
+            from: current_dir.join(name),
+            to: new_dir.join(name),
+            remove_source: true,
+        })
+        .collect();
+    // The logger keeps writing to the current file until restart
+    moves.push(FileMove {
+        from: active_log_file(),
+        to: new_dir.join(LOG_FILE_NAME),
+        remove_source: false,
+    });
+
+    // No settings writes while files are moving
+    let relocate = || relocate_data(&moves, &new_dir, &default_dir);
+    match app_handle.try_state::<SettingsSaver>() {
+        Some(saver) => {
+            saver.flush()?;
+            saver.write_through(relocate)?;
+        }
+        None => relocate()?,
+    }
+
+    // Anything newer than the copied file goes straight to the new location
+    if let Some(settings) = state.lock().unwrap().as_ref() {
+        save_settings(&app_handle, settings)?;
+    }
+
+    info!(
+        "📦 Data directory moved from {} to {}",
+        current_dir.display(),
+        new_dir.display()
+    );
+    Ok(())
+}
+
+fn relocate_data(moves: &[FileMove], new_dir: &Path, default_dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(new_dir)
+        .map_err(|e| format!("Failed to create {}: {}", new_dir.display(), e))?;
+    check_writable(new_dir)?;
+
+    let moves: Vec<&FileMove> = moves.iter().filter(|m| m.from.is_file()).collect();
+    if let Some(existing) = moves.iter().find(|m| m.to.exists()) {
+        return Err(format!(
+            "{} already exists; choose an empty directory",
+            existing.to.display()
+        ));
+    }
+
+    let mut copied = Vec::new();
+    for file_move in &moves {
+        if let Err(e) = fs::copy(&file_move.from, &file_move.to) {
+            remove_files(&copied);
+            return Err(format!(
+                "Failed to copy {}: {}",
+                file_move.from.display(),
+                e
+            ));
+        }
+        copied.push(file_move.to.clone());
+    }
+
+    // Moving back to the default location just drops the pointer
+    let pointer = default_dir.join(DATA_DIR_POINTER);
+    let pointer_result = if same_dir(new_dir, default_dir) {
+        match fs::remove_file(&pointer) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+            _ => Ok(()),
+        }
+    } else {
+        write_atomic(&pointer, &new_dir.to_string_lossy())
+    };
+    if let Err(e) = pointer_result {
+        remove_files(&copied);
+        return Err(format!("Failed to record the new data directory: {}", e));
+    }
+
+    for file_move in moves.iter().filter(|m| m.remove_source) {
+        if let Err(e) = fs::remove_file(&file_move.from) {
+            warn!(
+                "⚠️  Copied but couldn't remove {}: {}",
+                file_move.from.display(),
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+fn check_writable(dir: &Path) -> Result<(), String> {
+    let probe = dir.join(".whisprgpt-write-test");
+    fs::write(&probe, b"")
+        .and_then(|_| fs::remove_file(&probe))
+        .map_err(|e| format!("{} is not writable: {}", dir.display(), e))
+}
+
+fn remove_files(paths: &[PathBuf]) {
+    for path in paths {
+        let _ = fs::remove_file(path);
+    }
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+struct FileMove {
+    from: PathBuf,
+    to: PathBuf,
+    remove_source: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::settings::resolve_app_dir;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("whisprgpt-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn data_moves(from: &Path, to: &Path) -> Vec<FileMove> {
+        DATA_FILES
+            .iter()
+            .map(|name| FileMove {
+                from: from.join(name),
+                to: to.join(name),
+                remove_source: true,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn data_moves_to_the_new_dir() {
+        let old_dir = temp_dir("relocate-old");
+        let new_dir = temp_dir("relocate-root").join("data");
+        fs::write(old_dir.join(SETTINGS_FILE), "{}").unwrap();
+        fs::write(old_dir.join("ui_state.json"), r#"{"route":"/"}"#).unwrap();
+
+        relocate_data(&data_moves(&old_dir, &new_dir), &new_dir, &old_dir).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(new_dir.join(SETTINGS_FILE)).unwrap(),
+            "{}"
+        );
+        assert_eq!(
+            fs::read_to_string(new_dir.join("ui_state.json")).unwrap(),
+            r#"{"route":"/"}"#
+        );
+        assert!(!new_dir.join("notes.json").exists());
+        assert!(!old_dir.join(SETTINGS_FILE).exists());
+        assert!(!old_dir.join("ui_state.json").exists());
+        assert_eq!(resolve_app_dir(&old_dir), new_dir);
+
+        // And back again, which drops the pointer
+        relocate_data(&data_moves(&new_dir, &old_dir), &old_dir, &old_dir).unwrap();
+        assert!(old_dir.join(SETTINGS_FILE).exists());
+        assert!(!old_dir.join(DATA_DIR_POINTER).exists());
+        assert_eq!(resolve_app_dir(&old_dir), old_dir);
+    }
+
+    #[test]
+    fn failed_copy_rolls_back() {
+        let old_dir = temp_dir("rollback-old");
+        let new_dir = temp_dir("rollback-new");
+        fs::write(old_dir.join(SETTINGS_FILE), "{}").unwrap();
+        fs::write(old_dir.join("ui_state.json"), "{}").unwrap();
+
+        // settings.json copies fine, then ui_state.json can't be written
+        fs::create_dir(new_dir.join("blocked")).unwrap();
+        let mut moves = data_moves(&old_dir, &new_dir);
+        moves[2].to = new_dir
+            .join("blocked")
+            .join("missing")
+            .join("ui_state.json");
+
+        let result = relocate_data(&moves, &new_dir, &old_dir);
+        assert!(result.unwrap_err().starts_with("Failed to copy"));
+
+        assert!(!new_dir.join(SETTINGS_FILE).exists());
+        assert!(old_dir.join(SETTINGS_FILE).exists());
+        assert!(old_dir.join("ui_state.json").exists());
+        assert!(!old_dir.join(DATA_DIR_POINTER).exists());
+    }
+
+    #[test]
+    fn existing_files_are_not_overwritten() {
+        let old_dir = temp_dir("conflict-old");
+        let new_dir = temp_dir("conflict-new");
+        fs::write(old_dir.join(SETTINGS_FILE), "{}").unwrap();
+        fs::write(new_dir.join(SETTINGS_FILE), "theirs").unwrap();
+
+        assert!(relocate_data(&data_moves(&old_dir, &new_dir), &new_dir, &old_dir).is_err());
+        assert_eq!(
+            fs::read_to_string(new_dir.join(SETTINGS_FILE)).unwrap(),
+            "theirs"
+        );
+        assert!(old_dir.join(SETTINGS_FILE).exists());
+    }
+}
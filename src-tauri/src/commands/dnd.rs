@@ -0,0 +1,100 @@
+// commands/dnd.rs
+// Do Not Disturb: while on, anything that would pull the user's attention
+// (flashing the window to the front, scheduled shows) is held back and
+// summarized when DND is turned off again.
+
+use crate::commands::settings::update_settings;
+use crate::types::WhisperSettings;
+use log::info;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+
+// ============================================================================
+// Set DND Command
+// ============================================================================
+// Returns what was suppressed while DND was on (empty when turning it on).
+// The same list goes out as a "dnd-summary" event so the UI can replay it.
+#[tauri::command]
+pub fn set_dnd_command(
+    app_handle: AppHandle,
+    state: State<Mutex<Option<WhisperSettings>>>,
+    dnd: State<DoNotDisturb>,
+    enabled: bool,
+) -> Result<Vec<String>, String> {
+    update_settings(&app_handle, &state, |settings| {
+        settings.do_not_disturb = enabled
+    })?;
+
+    let suppressed = dnd.set(enabled);
+    info!("🔕 Do Not Disturb {}", if enabled { "on" } else { "off" });
+
+    if !suppressed.is_empty() {
+        info!(
+            "🔔 {} interruption(s) held back during DND",
+            suppressed.len()
+        );
+        let _ = app_handle.emit_all("dnd-summary", &suppressed);
+    }
+    Ok(suppressed)
+}
+
+// Gate for attention-grabbing behavior. Returns false (and queues `what` for
+// the summary) while DND is on.
+pub fn allow_interruption(app_handle: &AppHandle, what: &str) -> bool {
+    match app_handle.try_state::<DoNotDisturb>() {
+        Some(dnd) => dnd.allow(what),
+        None => true,
+    }
+}
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+// Managed state: mirrors the do_not_disturb setting (applied in setup() and by
+// set_dnd_command) plus what was suppressed while it was on
+#[derive(Default)]
+pub struct DoNotDisturb {
+    enabled: AtomicBool,
+    suppressed: Mutex<Vec<String>>,
+}
+
+impl DoNotDisturb {
+    // Turning DND off hands back (and clears) everything that was suppressed
+    pub fn set(&self, enabled: bool) -> Vec<String> {
+        self.enabled.store(enabled, Ordering::SeqCst);
+        if enabled {
+            return Vec::new();
+        }
+        std::mem::take(&mut *self.suppressed.lock().unwrap())
+    }
+
+    fn allow(&self, what: &str) -> bool {
+        if !self.enabled.load(Ordering::SeqCst) {
+            return true;
+        }
+        info!("🔕 Suppressed during DND: {}", what);
+        self.suppressed.lock().unwrap().push(what.to_string());
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interruptions_are_suppressed_and_summarized() {
+        let dnd = DoNotDisturb::default();
+        assert!(dnd.allow("flash to front"));
+
+        assert!(dnd.set(true).is_empty());
+        assert!(!dnd.allow("flash to front"));
+        assert!(!dnd.allow("scheduled show"));
+
+        assert_eq!(dnd.set(false), vec!["flash to front", "scheduled show"]);
+        assert!(dnd.allow("flash to front"));
+        assert!(dnd.set(false).is_empty());
+    }
+}
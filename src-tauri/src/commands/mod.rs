@@ -3,8 +3,24 @@
 // Commands are functions that your React frontend can call.
 
 pub mod auth;
+pub mod data_dir;
+pub mod dnd;
+pub mod logs;
+pub mod network;
+pub mod page;
+pub mod recording;
+pub mod remote_config;
+pub mod schedule;
+pub mod screen;
+pub mod self_test;
+pub mod session;
 pub mod settings;
 pub mod shortcuts;
+pub mod snapshot;
+pub mod templates;
+pub mod typing;
+pub mod ui_state;
+pub mod views;
 pub mod window;
 pub mod whispr;
 // pub mod permissions;
@@ -0,0 +1,133 @@
+// commands/network.rs
+// Proxy support for users behind corporate proxies. The proxy applies to our
+// own HTTP requests right away; the webview only picks it up at startup.
+
+use crate::commands::settings::update_settings;
+use crate::types::WhisperSettings;
+use log::{info, warn};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, State};
+use url::Url;
+
+const PROXY_SCHEMES: [&str; 2] = ["http", "https"];
+
+// ============================================================================
+// Set Proxy Command
+// ============================================================================
+// An empty or missing URL removes the proxy. Returns true because the webview
+// only reads the proxy at startup, so the change needs a restart to be complete.
+#[tauri::command]
+pub fn set_proxy_command(
+    app_handle: AppHandle,
+    state: State<Mutex<Option<WhisperSettings>>>,
+    proxy_url: Option<String>,
+) -> Result<bool, String> {
+    let proxy_url = match proxy_url.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(proxy_url) => Some(validate_proxy_url(proxy_url)?.to_string()),
+    };
+
+    match &proxy_url {
+        Some(proxy_url) => info!("🌐 Proxy set to {}", redact_proxy_url(proxy_url)),
+        None => info!("🌐 Proxy removed"),
+    }
+
+    update_settings(&app_handle, &state, |settings| {
+        settings.proxy_url = proxy_url
+    })?;
+    Ok(true)
+}
+
+// ============================================================================
+// Proxy Helpers
+// ============================================================================
+// Accepts http(s)://[user:pass@]host[:port]
+pub fn validate_proxy_url(proxy_url: &str) -> Result<Url, String> {
+    let url = Url::parse(proxy_url).map_err(|e| format!("Invalid proxy URL: {}", e))?;
+
+    if !PROXY_SCHEMES.contains(&url.scheme()) {
+        return Err(format!("Unsupported proxy scheme: {}", url.scheme()));
+    }
+    if matches!(url.host_str(), None | Some("")) {
+        return Err("Proxy URL has no host".to_string());
+    }
+    if url.path() != "/" || url.query().is_some() || url.fragment().is_some() {
+        return Err("Proxy URL must not have a path, query or fragment".to_string());
+    }
+
+    Ok(url)
+}
+
+// The proxy URL with any password masked, for logs
+pub fn redact_proxy_url(proxy_url: &str) -> String {
+    match Url::parse(proxy_url) {
+        Ok(mut url) => {
+            if url.password().is_some() {
+                let _ = url.set_password(Some("***"));
+            }
+            url.to_string()
+        }
+        Err(_) => "<invalid>".to_string(),
+    }
+}
+
+// Every reqwest client in the app should come from here so it honors the proxy.
+// Credentials in the URL are sent as proxy basic auth by reqwest.
+pub fn http_client(timeout: Duration, proxy_url: Option<&str>) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder().timeout(timeout);
+
+    if let Some(proxy_url) = proxy_url {
+        let proxy = reqwest::Proxy::all(validate_proxy_url(proxy_url)?)
+            .map_err(|e| format!("Invalid proxy: {}", e))?;
+        builder = builder.proxy(proxy);
+    }
+
+    builder
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+// Must run before the main window is created (i.e. before the Tauri builder
+// runs), since webviews read their proxy configuration at creation time.
+pub fn apply_webview_proxy(proxy_url: &str) {
+    let url = match validate_proxy_url(proxy_url) {
+        Ok(url) => url,
+        Err(e) => {
+            warn!("⚠️  Ignoring proxy setting: {}", e);
+            return;
+        }
+    };
+
+    // WebView2 takes Chromium flags; credentials are prompted for by the webview
+    #[cfg(target_os = "windows")]
+    {
+        let server = format!(
+            "{}://{}:{}",
+            url.scheme(),
+            url.host_str().unwrap_or_default(),
+            url.port_or_known_default().unwrap_or(80)
+        );
+        std::env::set_var(
+            "WEBVIEW2_ADDITIONAL_BROWSER_ARGUMENTS",
+            format!("--proxy-server={}", server),
+        );
+    }
+
+    // WebKitGTK falls back to the standard proxy environment variables
+    #[cfg(target_os = "linux")]
+    {
+        std::env::set_var("http_proxy", url.as_str());
+        std::env::set_var("https_proxy", url.as_str());
+    }
+
+    // WKWebView in Tauri v1 always follows the system proxy settings
+    #[cfg(target_os = "macos")]
+    warn!(
+        "⚠️  macOS webview uses the system proxy; {} only applies to app requests",
+        redact_proxy_url(url.as_str())
+    );
+
+    #[cfg(not(target_os = "macos"))]
+    info!("🌐 Webview proxy: {}", redact_proxy_url(url.as_str()));
+}
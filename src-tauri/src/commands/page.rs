@@ -0,0 +1,168 @@
+// commands/page.rs
+// Reads information back from the provider page. Tauri v1's eval() can't
+// return values, so the injected script reports back over IPC instead.
+// Provider pages get IPC access for exactly those report commands - see
+// allow_provider_ipc and is_command_allowed.
+
+use crate::commands::whispr::{is_dashboard_url, provider_for_url, PROVIDERS};
+use crate::commands::window::require_main_window;
+use crate::limits::MAX_PAGE_REPORT_BYTES;
+use log::{info, warn};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::scope::ipc::RemoteDomainAccessScope;
+use tauri::{AppHandle, Invoke, Manager, State, Window};
+use url::Url;
+
+const PAGE_TITLE_TIMEOUT: Duration = Duration::from_secs(1);
+
+// The only commands a provider page may invoke
+const PROVIDER_PAGE_COMMANDS: [&str; 3] = [
+    "report_page_title_command",
+    "report_provider_url_command",
+    "report_text_input_focus_command",
+];
+
+// ============================================================================
+// Get Page Title Command
+// ============================================================================
+// document.title of the main window's page, e.g. the conversation name. Empty
+// if the page doesn't answer in time; the app name while the dashboard is
+// shown.
+#[tauri::command]
+pub async fn get_page_title_command(
+    app_handle: AppHandle,
+    requests: State<'_, PageTitleRequests>,
+) -> Result<String, String> {
+    page_title(&require_main_window(&app_handle)?, &requests).await
+}
+
+pub async fn page_title(window: &Window, requests: &PageTitleRequests) -> Result<String, String> {
+    if is_dashboard_url(&window.url()) {
+        return Ok(window.app_handle().package_info().name.clone());
+    }
+
+    let id = requests.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+    let (tx, rx) = mpsc::channel();
+    requests.pending.lock().unwrap().insert(id, tx);
+
+    let script = format!(
+        "window.__TAURI_INVOKE__ && window.__TAURI_INVOKE__('report_page_title_command', \
+         {{ id: {}, title: document.title }}).catch(function () {{}})",
+        id
+    );
+    if let Err(e) = window.eval(&script) {
+        requests.pending.lock().unwrap().remove(&id);
+        return Err(format!("Failed to read page title: {}", e));
+    }
+
+    // Wait off the async runtime; the report arrives through the IPC handler
+    let title = tauri::async_runtime::spawn_blocking(move || rx.recv_timeout(PAGE_TITLE_TIMEOUT))
+        .await
+        .map_err(|e| format!("Failed to read page title: {}", e))?
+        .unwrap_or_default();
+
+    requests.pending.lock().unwrap().remove(&id);
+    Ok(title)
+}
+
+// The main window's title follows its page, so the overlay shows the current
+// conversation. Called on page loads and when a provider page reports a new
+// URL (providers switch conversations without a page load).
+pub fn sync_window_title(app_handle: &AppHandle) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = update_window_title(&app_handle).await {
+            warn!("⚠️  Failed to update the window title: {}", e);
+        }
+    });
+}
+
+async fn update_window_title(app_handle: &AppHandle) -> Result<(), String> {
+    let window = require_main_window(app_handle)?;
+    let title = page_title(&window, &app_handle.state::<PageTitleRequests>()).await?;
+    // No answer in time; keep the title we have
+    if title.is_empty() {
+        return Ok(());
+    }
+    window
+        .set_title(&title)
+        .map_err(|e| format!("Failed to set window title: {}", e))
+}
+
+// Called by the script injected in get_page_title_command
+#[tauri::command]
+pub fn report_page_title_command(requests: State<PageTitleRequests>, id: u64, title: String) {
+    let title = if title.len() > MAX_PAGE_REPORT_BYTES {
+        warn!("⚠️  Ignoring oversized page title ({} bytes)", title.len());
+        String::new()
+    } else {
+        title
+    };
+
+    if let Some(tx) = requests.pending.lock().unwrap().remove(&id) {
+        let _ = tx.send(title);
+    }
+}
+
+// ============================================================================
+// Provider Page IPC
+// ============================================================================
+// Lets the provider domains reach the invoke handler at all. Which commands
+// they can actually run is decided by is_command_allowed.
+pub fn allow_provider_ipc(app_handle: &AppHandle) {
+    for (_, url) in PROVIDERS {
+        let Some(host) = Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(String::from))
+        else {
+            continue;
+        };
+        app_handle
+            .ipc_scope()
+            .configure_remote_access(RemoteDomainAccessScope::new(host).add_window("main"));
+    }
+    info!(
+        "🔐 Page report IPC enabled for {} providers",
+        PROVIDERS.len()
+    );
+}
+
+// Wraps the app's invoke handler so provider pages can only run the report
+// commands, never the rest of the app's commands
+pub fn guard_provider_ipc<F>(handler: F) -> impl Fn(Invoke) + Send + Sync + 'static
+where
+    F: Fn(Invoke) + Send + Sync + 'static,
+{
+    move |invoke: Invoke| {
+        let url = invoke.message.window_ref().url();
+        if !is_command_allowed(&url, invoke.message.command()) {
+            warn!(
+                "⚠️  Blocked '{}' invoked from {}",
+                invoke.message.command(),
+                url
+            );
+            invoke.resolver.reject("Command not allowed from this page");
+            return;
+        }
+        handler(invoke)
+    }
+}
+
+fn is_command_allowed(url: &Url, command: &str) -> bool {
+    provider_for_url(url).is_none() || PROVIDER_PAGE_COMMANDS.contains(&command)
+}
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+// Managed state: get_page_title_command calls waiting for their report
+#[derive(Default)]
+pub struct PageTitleRequests {
+    next_id: AtomicU64,
+    pending: Mutex<HashMap<u64, Sender<String>>>,
+}
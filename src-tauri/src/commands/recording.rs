@@ -0,0 +1,98 @@
+// commands/recording.rs
+// Detects running screen recording / screen sharing software, so the app can
+// react (e.g. hide itself from captures) before anything gets recorded.
+
+use crate::commands::window::set_content_protection;
+use crate::state::settings::load_settings;
+use crate::types::WhisperSettings;
+use log::{error, info};
+use std::sync::Mutex;
+use tauri::{Manager, State, Window};
+
+// ============================================================================
+// Detect Screen Recording Command
+// ============================================================================
+// Returns the configured recorder apps that are running right now (sorted,
+// lowercase). With auto_stealth_on_recording, a match also turns on content
+// protection for this window.
+#[tauri::command]
+pub fn detect_screen_recording_command(
+    window: Window,
+    state: State<Mutex<Option<WhisperSettings>>>,
+) -> Result<Vec<String>, String> {
+    let settings = match state.lock().unwrap().as_ref() {
+        Some(settings) => settings.clone(),
+        None => load_settings(&window.app_handle())?,
+    };
+
+    let mut system = sysinfo::System::new();
+    system.refresh_processes();
+    let process_names: Vec<String> = system
+        .processes()
+        .values()
+        .map(|process| process.name().to_string())
+        .collect();
+
+    let detected = running_recorders(&process_names, &settings.screen_recorder_apps);
+    if detected.is_empty() {
+        return Ok(detected);
+    }
+
+    info!(
+        "🎥 Screen recording software running: {}",
+        detected.join(", ")
+    );
+    if settings.auto_stealth_on_recording {
+        if let Err(e) = set_content_protection(&window, true) {
+            error!("❌ Failed to enable content protection: {}", e);
+        }
+    }
+    Ok(detected)
+}
+
+// The entries of `apps` that match a running process. Names are compared
+// case-insensitively and without a trailing ".exe".
+fn running_recorders(process_names: &[String], apps: &[String]) -> Vec<String> {
+    let running: Vec<String> = process_names
+        .iter()
+        .map(|name| normalize_process_name(name))
+        .collect();
+
+    let mut detected: Vec<String> = apps
+        .iter()
+        .map(|app| normalize_process_name(app))
+        .filter(|app| !app.is_empty() && running.contains(app))
+        .collect();
+    detected.sort();
+    detected.dedup();
+    detected
+}
+
+fn normalize_process_name(name: &str) -> String {
+    let name = name.trim().to_lowercase();
+    match name.strip_suffix(".exe") {
+        Some(stem) => stem.to_string(),
+        None => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn running_recorders_match_configured_apps() {
+        let processes = names(&["explorer.exe", "obs64.exe", "Zoom.exe", "obsidian.exe"]);
+        let apps = names(&["OBS64", "zoom", "loom", "obs", "zoom"]);
+
+        assert_eq!(
+            running_recorders(&processes, &apps),
+            names(&["obs64", "zoom"])
+        );
+        assert!(running_recorders(&processes, &[]).is_empty());
+    }
+}
@@ -0,0 +1,250 @@
+// commands/remote_config.rs
+// Managed deployments: an admin-hosted JSON config that is merged over the
+// user's settings, optionally signed, and can lock fields against changes.
+// Only available in builds with the "remote-config" feature; builds that pin
+// a public key (WHISPRGPT_CONFIG_PUBLIC_KEY, base64 ed25519, at build time)
+// reject configs without a valid signature.
+//
+// Config format:
+//   { "settings": { "proxy_url": "http://proxy.corp:8080" },
+//     "locked_fields": ["proxy_url"] }
+// The signature (base64, over the exact bytes of the config) is served next
+// to it at the same URL with ".sig" appended to the path.
+
+use crate::commands::network::http_client;
+use crate::commands::settings::persist_settings;
+use crate::limits::{check_settings, check_size, MAX_SETTINGS_BYTES};
+use crate::state::settings::load_settings;
+use crate::types::WhisperSettings;
+use log::{info, warn};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, State};
+use url::Url;
+
+const REMOTE_CONFIG_TIMEOUT: Duration = Duration::from_secs(15);
+
+const CONFIG_PUBLIC_KEY: Option<&str> = option_env!("WHISPRGPT_CONFIG_PUBLIC_KEY");
+
+// ============================================================================
+// Apply Remote Config Command
+// ============================================================================
+#[tauri::command]
+pub async fn apply_remote_config_command(
+    app_handle: AppHandle,
+    state: State<'_, Mutex<Option<WhisperSettings>>>,
+    url: String,
+) -> Result<WhisperSettings, String> {
+    if !cfg!(feature = "remote-config") {
+        return Err("Remote config is not enabled in this build".to_string());
+    }
+
+    let url = Url::parse(&url).map_err(|e| format!("Invalid config URL: {}", e))?;
+    if url.scheme() != "https" {
+        return Err("Remote config must be served over https".to_string());
+    }
+
+    let proxy_url = match state.lock().unwrap().as_ref() {
+        Some(settings) => settings.proxy_url.clone(),
+        None => load_settings(&app_handle)?.proxy_url,
+    };
+    let client = http_client(REMOTE_CONFIG_TIMEOUT, proxy_url.as_deref())?;
+
+    let body = fetch(&client, &url)
+        .await?
+        .ok_or_else(|| format!("Remote config not found: {}", url))?;
+    check_size("Remote config", body.len(), MAX_SETTINGS_BYTES)?;
+
+    let mut signature_url = url.clone();
+    signature_url.set_path(&format!("{}.sig", url.path()));
+    let signature = fetch(&client, &signature_url).await?;
+
+    match (CONFIG_PUBLIC_KEY, signature) {
+        (Some(public_key), Some(signature)) => verify_signature(public_key, &body, &signature)?,
+        (Some(_), None) => return Err("Remote config is not signed".to_string()),
+        (None, Some(_)) => {
+            warn!("⚠️  Remote config is signed, but this build has no key to check it")
+        }
+        (None, None) => {}
+    }
+
+    let config: RemoteConfig =
+        serde_json::from_slice(&body).map_err(|e| format!("Invalid remote config: {}", e))?;
+
+    // Applied in one go under the settings lock, bypassing the locked-field
+    // check that user changes go through
+    let mut settings_lock = state.lock().unwrap();
+    let current = match settings_lock.as_ref() {
+        Some(settings) => settings.clone(),
+        None => load_settings(&app_handle)?,
+    };
+    let merged = merge_remote_config(&current, config)?;
+    check_settings(&merged)?;
+    persist_settings(&app_handle, &merged)?;
+    *settings_lock = Some(merged.clone());
+
+    info!(
+        "🏢 Applied remote config from {} ({} locked fields)",
+        url,
+        merged.locked_fields.len()
+    );
+    Ok(merged)
+}
+
+// The response body, or None for a 404
+async fn fetch(client: &reqwest::Client, url: &Url) -> Result<Option<Vec<u8>>, String> {
+    let response = client
+        .get(url.as_str())
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+
+    if response.status().as_u16() == 404 {
+        return Ok(None);
+    }
+    let response = response
+        .error_for_status()
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", url, e))?;
+    Ok(Some(body.to_vec()))
+}
+
+#[cfg(feature = "remote-config")]
+fn verify_signature(public_key: &str, body: &[u8], signature: &[u8]) -> Result<(), String> {
+    use base64::Engine;
+    use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+    let base64 = base64::engine::general_purpose::STANDARD;
+    let public_key: [u8; 32] = base64
+        .decode(public_key.trim())
+        .ok()
+        .and_then(|key| key.try_into().ok())
+        .ok_or("Invalid pinned config public key")?;
+    let signature: [u8; 64] = base64
+        .decode(String::from_utf8_lossy(signature).trim())
+        .ok()
+        .and_then(|signature| signature.try_into().ok())
+        .ok_or("Invalid remote config signature")?;
+
+    VerifyingKey::from_bytes(&public_key)
+        .map_err(|e| format!("Invalid pinned config public key: {}", e))?
+        .verify(body, &Signature::from_bytes(&signature))
+        .map_err(|_| "Remote config signature doesn't match".to_string())
+}
+
+#[cfg(not(feature = "remote-config"))]
+fn verify_signature(_public_key: &str, _body: &[u8], _signature: &[u8]) -> Result<(), String> {
+    Err("Remote config is not enabled in this build".to_string())
+}
+
+// Remote values win over the user's, and the remote lock list replaces the
+// current one. Unknown fields are rejected rather than silently dropped.
+fn merge_remote_config(
+    current: &WhisperSettings,
+    config: RemoteConfig,
+) -> Result<WhisperSettings, String> {
+    let mut merged =
+        serde_json::to_value(current).map_err(|e| format!("Failed to read settings: {}", e))?;
+    let fields = merged.as_object_mut().ok_or("Failed to read settings")?;
+
+    if let Some(field) = config
+        .settings
+        .keys()
+        .chain(&config.locked_fields)
+        .find(|field| field.as_str() == "locked_fields" || !fields.contains_key(*field))
+    {
+        return Err(format!("Unknown setting in remote config: {}", field));
+    }
+
+    fields.extend(config.settings);
+    fields.insert(
+        "locked_fields".to_string(),
+        serde_json::json!(config.locked_fields),
+    );
+
+    serde_json::from_value(merged).map_err(|e| format!("Invalid remote config: {}", e))
+}
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct RemoteConfig {
+    #[serde(default)]
+    settings: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    locked_fields: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config(value: serde_json::Value) -> RemoteConfig {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn remote_values_override_the_users() {
+        let mut current = WhisperSettings::default();
+        current.proxy_url = Some("http://home-proxy:3128".to_string());
+        current.llm = "grok".to_string();
+
+        let merged = merge_remote_config(
+            &current,
+            config(json!({ "settings": { "proxy_url": "http://proxy.corp:8080" } })),
+        )
+        .unwrap();
+        assert_eq!(merged.proxy_url.as_deref(), Some("http://proxy.corp:8080"));
+        // Fields the config doesn't mention keep the user's value
+        assert_eq!(merged.llm, "grok");
+    }
+
+    #[test]
+    fn remote_lock_list_replaces_the_current_one() {
+        let mut current = WhisperSettings::default();
+        current.locked_fields = vec!["opacity".to_string(), "llm".to_string()];
+
+        let merged =
+            merge_remote_config(&current, config(json!({ "locked_fields": ["proxy_url"] })))
+                .unwrap();
+        assert_eq!(merged.locked_fields, ["proxy_url"]);
+
+        let merged = merge_remote_config(&current, config(json!({}))).unwrap();
+        assert!(merged.locked_fields.is_empty());
+    }
+
+    #[test]
+    fn unknown_remote_fields_are_rejected() {
+        let current = WhisperSettings::default();
+
+        for config_value in [
+            json!({ "settings": { "removed_in_v2": true } }),
+            json!({ "locked_fields": ["removed_in_v2"] }),
+            // The lock list is only set through "locked_fields" itself
+            json!({ "settings": { "locked_fields": [] } }),
+            json!({ "locked_fields": ["locked_fields"] }),
+        ] {
+            assert!(
+                merge_remote_config(&current, config(config_value.clone())).is_err(),
+                "{}",
+                config_value
+            );
+        }
+
+        // Known fields with the wrong type don't get through either
+        assert!(merge_remote_config(
+            &current,
+            config(json!({ "settings": { "opacity": "high" } }))
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,128 @@
+// commands/schedule.rs
+// Delayed actions (e.g. "show for 10 seconds, then hide") built on the
+// existing window/navigation commands
+
+use crate::commands::dnd::allow_interruption;
+use crate::commands::whispr::navigate_to_dashboard_command;
+use crate::commands::window::{
+    hide_window_command, require_main_window, saved_opacity, show_window_command,
+};
+use log::{error, info};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+// ============================================================================
+// Schedule Action Command
+// ============================================================================
+// Returns an id that can be passed to cancel_scheduled_action_command.
+#[tauri::command]
+pub fn schedule_action_command(
+    app_handle: AppHandle,
+    scheduler: State<ActionScheduler>,
+    action: String,
+    delay_ms: u32,
+) -> Result<u64, String> {
+    let action = ScheduledAction::parse(&action)?;
+    let id = scheduler.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+
+    // Dropping the sender (cancel) wakes the timer thread without running the action
+    let (cancel_tx, cancel_rx) = mpsc::channel::<()>();
+    scheduler.pending.lock().unwrap().insert(id, cancel_tx);
+
+    info!("⏰ Scheduled {:?} in {}ms (id {})", action, delay_ms, id);
+
+    std::thread::spawn(move || {
+        let fired = matches!(
+            cancel_rx.recv_timeout(Duration::from_millis(delay_ms as u64)),
+            Err(RecvTimeoutError::Timeout)
+        );
+
+        app_handle
+            .state::<ActionScheduler>()
+            .pending
+            .lock()
+            .unwrap()
+            .remove(&id);
+
+        if fired {
+            run_action(&app_handle, action);
+        }
+    });
+
+    Ok(id)
+}
+
+// ============================================================================
+// Cancel Scheduled Action Command
+// ============================================================================
+// Returns false if the action already ran or the id is unknown.
+#[tauri::command]
+pub fn cancel_scheduled_action_command(
+    scheduler: State<ActionScheduler>,
+    id: u64,
+) -> Result<bool, String> {
+    let cancelled = scheduler.pending.lock().unwrap().remove(&id).is_some();
+    if cancelled {
+        info!("⏰ Cancelled scheduled action {}", id);
+    }
+    Ok(cancelled)
+}
+
+fn run_action(app_handle: &AppHandle, action: ScheduledAction) {
+    info!("⏰ Running scheduled action: {:?}", action);
+
+    let result = match action {
+        ScheduledAction::Hide => require_main_window(app_handle).and_then(hide_window_command),
+        ScheduledAction::Show if !allow_interruption(app_handle, "scheduled show") => Ok(()),
+        ScheduledAction::Show => require_main_window(app_handle)
+            .and_then(|w| show_window_command(w, saved_opacity(app_handle))),
+        ScheduledAction::NavigateHome => navigate_to_dashboard_command(app_handle.clone()),
+        // Audio lives in the frontend, so muting is delegated to it
+        ScheduledAction::Mute => require_main_window(app_handle).and_then(|w| {
+            w.emit("mute", ())
+                .map_err(|e| format!("Failed to emit mute event: {}", e))
+        }),
+    };
+
+    if let Err(e) = result {
+        error!("❌ Scheduled action {:?} failed: {}", action, e);
+    }
+}
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ScheduledAction {
+    Hide,
+    Show,
+    NavigateHome,
+    Mute,
+}
+
+impl ScheduledAction {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "hide" => Ok(Self::Hide),
+            "show" => Ok(Self::Show),
+            "navigate-home" => Ok(Self::NavigateHome),
+            "mute" => Ok(Self::Mute),
+            _ => Err(format!(
+                "Unknown action '{}' (expected hide, show, navigate-home or mute)",
+                name
+            )),
+        }
+    }
+}
+
+// Managed state: cancel handles for actions that haven't fired yet
+#[derive(Default)]
+pub struct ActionScheduler {
+    next_id: AtomicU64,
+    pending: Mutex<HashMap<u64, Sender<()>>>,
+}
@@ -0,0 +1,350 @@
+// commands/screen.rs
+// Reading the screen itself (not our webview). Coordinates are global physical
+// pixels, the same space as window positions and tauri::Monitor.
+
+use crate::types::WindowGeometry;
+use base64::Engine;
+use log::info;
+use screenshots::image::RgbaImage;
+use screenshots::{DisplayInfo, Screen};
+use tauri::{AppHandle, Manager};
+
+// ============================================================================
+// Get Pixel Color Command
+// ============================================================================
+// Returns the color at (x, y) as "#rrggbb"
+#[tauri::command]
+pub fn get_pixel_color_command(x: i32, y: i32) -> Result<String, String> {
+    let screens = Screen::all().map_err(|e| format!("Failed to list displays: {}", e))?;
+
+    let screen = screens
+        .iter()
+        .find(|screen| contains_point(physical_bounds(&screen.display_info), x, y))
+        .ok_or_else(|| format!("Point ({}, {}) is not on any display", x, y))?;
+
+    let bounds = physical_bounds(&screen.display_info);
+    let (local_x, local_y) = to_display_units(&screen.display_info, x - bounds.x, y - bounds.y);
+
+    let image = screen
+        .capture_area(local_x, local_y, 1, 1)
+        .map_err(|e| format!("Failed to read pixel: {}", e))?;
+    if image.width() == 0 || image.height() == 0 {
+        return Err("Failed to read pixel: empty capture".to_string());
+    }
+
+    let color = hex_color(image.get_pixel(0, 0).0);
+    info!("🎨 Pixel at ({}, {}): {}", x, y, color);
+    Ok(color)
+}
+
+// ============================================================================
+// Capture All Monitors Command
+// ============================================================================
+// One PNG per monitor, or with `stitch` a single PNG of the whole virtual
+// desktop with every monitor in its place (gaps stay transparent).
+#[tauri::command]
+pub async fn capture_all_monitors_command(
+    app_handle: AppHandle,
+    stitch: Option<bool>,
+) -> Result<Vec<MonitorCapture>, String> {
+    let stitch = stitch.unwrap_or(false);
+
+    // Capturing and encoding takes a while; keep it off the async runtime
+    let captures = tauri::async_runtime::spawn_blocking(move || capture_all_monitors(stitch))
+        .await
+        .map_err(|e| format!("Failed to capture monitors: {}", e))??;
+
+    info!(
+        "📸 Captured {} monitor(s){}",
+        captures.len(),
+        if stitch { " into one image" } else { "" }
+    );
+    let _ = app_handle.emit_all(
+        "capture-complete",
+        captures.iter().map(|c| c.bounds).collect::<Vec<_>>(),
+    );
+    Ok(captures)
+}
+
+fn capture_all_monitors(stitch: bool) -> Result<Vec<MonitorCapture>, String> {
+    let screens = Screen::all().map_err(|e| format!("Failed to list displays: {}", e))?;
+    if screens.is_empty() {
+        return Err("No displays found".to_string());
+    }
+
+    let mut shots = Vec::new();
+    for screen in &screens {
+        let image = screen.capture().map_err(|e| {
+            format!(
+                "Failed to capture display {}: {}",
+                screen.display_info.id, e
+            )
+        })?;
+        shots.push((screen.display_info, image));
+    }
+
+    if !stitch {
+        return shots
+            .iter()
+            .map(|(display, image)| {
+                let (width, height) = image.dimensions();
+                Ok(MonitorCapture {
+                    bounds: physical_bounds(display),
+                    scale_factor: display.scale_factor as f64,
+                    png_base64: encode_png_base64(width, height, image.as_raw())?,
+                })
+            })
+            .collect();
+    }
+
+    let scale = stitch_scale(shots.iter().map(|(d, _)| d.scale_factor as f64));
+    let bounds: Vec<WindowGeometry> = shots
+        .iter()
+        .map(|(d, _)| scale_bounds(display_bounds(d), scale))
+        .collect();
+    let desktop = union_bounds(&bounds);
+    let mut canvas = vec![0u8; desktop.width as usize * desktop.height as usize * 4];
+    for ((_, image), monitor) in shots.iter().zip(&bounds) {
+        blit_scaled(&mut canvas, desktop, image, *monitor);
+    }
+
+    Ok(vec![MonitorCapture {
+        bounds: desktop,
+        scale_factor: scale,
+        png_base64: encode_png_base64(desktop.width, desktop.height, &canvas)?,
+    }])
+}
+
+// The stitched desktop is laid out in display units times this. On macOS
+// those are points, and each display's own scale would put a 1x display
+// inside the span of a 2x one next to it, so every display gets the largest
+// scale (HiDPI ones keep full detail, the others are resampled up).
+// Elsewhere display units are already pixels.
+fn stitch_scale(scale_factors: impl Iterator<Item = f64>) -> f64 {
+    if cfg!(target_os = "macos") {
+        scale_factors.fold(1.0, f64::max)
+    } else {
+        1.0
+    }
+}
+
+// Smallest rectangle containing every monitor
+fn union_bounds(monitors: &[WindowGeometry]) -> WindowGeometry {
+    let left = monitors.iter().map(|m| m.x as i64).min().unwrap_or(0);
+    let top = monitors.iter().map(|m| m.y as i64).min().unwrap_or(0);
+    let right = monitors
+        .iter()
+        .map(|m| m.x as i64 + m.width as i64)
+        .max()
+        .unwrap_or(0);
+    let bottom = monitors
+        .iter()
+        .map(|m| m.y as i64 + m.height as i64)
+        .max()
+        .unwrap_or(0);
+
+    WindowGeometry {
+        x: left as i32,
+        y: top as i32,
+        width: (right - left) as u32,
+        height: (bottom - top) as u32,
+    }
+}
+
+// Copies `image` into the RGBA canvas at the monitor's physical rectangle.
+// A capture whose resolution doesn't match the monitor's physical size (a
+// display that reports logical sizes) is resampled so everything lines up.
+fn blit_scaled(
+    canvas: &mut [u8],
+    desktop: WindowGeometry,
+    image: &RgbaImage,
+    monitor: WindowGeometry,
+) {
+    let (src_width, src_height) = image.dimensions();
+    if src_width == 0 || src_height == 0 {
+        return;
+    }
+    let src = image.as_raw();
+    let offset_x = (monitor.x as i64 - desktop.x as i64) as usize;
+    let offset_y = (monitor.y as i64 - desktop.y as i64) as usize;
+
+    for y in 0..monitor.height as usize {
+        let src_y = y * src_height as usize / monitor.height as usize;
+        for x in 0..monitor.width as usize {
+            let src_x = x * src_width as usize / monitor.width as usize;
+            let from = (src_y * src_width as usize + src_x) * 4;
+            let to = ((offset_y + y) * desktop.width as usize + offset_x + x) * 4;
+            canvas[to..to + 4].copy_from_slice(&src[from..from + 4]);
+        }
+    }
+}
+
+fn encode_png_base64(width: u32, height: u32, rgba: &[u8]) -> Result<String, String> {
+    let mut png_bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png_bytes, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(rgba))
+        .map_err(|e| format!("Failed to encode capture PNG: {}", e))?;
+
+    Ok(base64::engine::general_purpose::STANDARD.encode(png_bytes))
+}
+
+// ============================================================================
+// Display Helpers
+// ============================================================================
+// macOS reports displays in points; everywhere else they're already pixels.
+// Each display is scaled by its own factor, like tauri::Monitor does.
+pub fn physical_bounds(display: &DisplayInfo) -> WindowGeometry {
+    #[cfg(target_os = "macos")]
+    let scale = display.scale_factor as f64;
+    #[cfg(not(target_os = "macos"))]
+    let scale = 1.0;
+
+    scale_bounds(display_bounds(display), scale)
+}
+
+fn display_bounds(display: &DisplayInfo) -> WindowGeometry {
+    WindowGeometry {
+        x: display.x,
+        y: display.y,
+        width: display.width,
+        height: display.height,
+    }
+}
+
+fn scale_bounds(bounds: WindowGeometry, scale: f64) -> WindowGeometry {
+    WindowGeometry {
+        x: (bounds.x as f64 * scale).round() as i32,
+        y: (bounds.y as f64 * scale).round() as i32,
+        width: (bounds.width as f64 * scale).round() as u32,
+        height: (bounds.height as f64 * scale).round() as u32,
+    }
+}
+
+// Display-relative physical pixels -> the units capture_area expects
+fn to_display_units(display: &DisplayInfo, x: i32, y: i32) -> (i32, i32) {
+    #[cfg(target_os = "macos")]
+    {
+        let scale = display.scale_factor as f64;
+        ((x as f64 / scale) as i32, (y as f64 / scale) as i32)
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = display;
+        (x, y)
+    }
+}
+
+fn contains_point(bounds: WindowGeometry, x: i32, y: i32) -> bool {
+    let (x, y) = (x as i64, y as i64);
+    x >= bounds.x as i64
+        && y >= bounds.y as i64
+        && x < bounds.x as i64 + bounds.width as i64
+        && y < bounds.y as i64 + bounds.height as i64
+}
+
+fn hex_color([r, g, b, _]: [u8; 4]) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct MonitorCapture {
+    // Physical pixels in desktop coordinates; the PNG has this exact size
+    // except when an unstitched capture comes back at another resolution.
+    // A stitched capture's bounds are display units times scale_factor (see
+    // stitch_scale).
+    pub bounds: WindowGeometry,
+    pub scale_factor: f64,
+    pub png_base64: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn geometry(x: i32, y: i32, width: u32, height: u32) -> WindowGeometry {
+        WindowGeometry {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn union_covers_monitors_left_of_and_above_the_primary() {
+        let desktop = union_bounds(&[
+            geometry(0, 0, 1920, 1080),
+            geometry(-1280, -200, 1280, 1024),
+        ]);
+        assert_eq!(desktop, geometry(-1280, -200, 3200, 1280));
+        assert_eq!(union_bounds(&[]), geometry(0, 0, 0, 0));
+    }
+
+    #[test]
+    fn mixed_dpi_displays_do_not_overlap_once_scaled() {
+        // A 2x laptop in points with a 1x display to its right
+        let retina = scale_bounds(geometry(0, 0, 1440, 900), 2.0);
+        let external = scale_bounds(geometry(1440, 0, 1920, 1080), 2.0);
+
+        assert_eq!(retina, geometry(0, 0, 2880, 1800));
+        assert_eq!(external, geometry(2880, 0, 3840, 2160));
+        assert!(!contains_point(
+            external,
+            retina.x + retina.width as i32 - 1,
+            0
+        ));
+        assert_eq!(
+            union_bounds(&[retina, external]),
+            geometry(0, 0, 6720, 2160)
+        );
+    }
+
+    #[test]
+    fn blit_resamples_into_the_monitor_rectangle() {
+        let desktop = geometry(-2, 0, 6, 2);
+        let mut canvas = vec![0u8; 6 * 2 * 4];
+        let red = [255, 0, 0, 255];
+        let blue = [0, 0, 255, 255];
+        let image = RgbaImage::from_raw(2, 1, [red, blue].concat()).unwrap();
+
+        // A 2x1 capture stretched over a 4x2 monitor at x=0
+        blit_scaled(&mut canvas, desktop, &image, geometry(0, 0, 4, 2));
+
+        let pixel = |x: usize, y: usize| -> [u8; 4] {
+            let at = (y * 6 + x) * 4;
+            canvas[at..at + 4].try_into().unwrap()
+        };
+        for y in 0..2 {
+            assert_eq!(pixel(0, y), [0, 0, 0, 0], "gap left of the monitor");
+            assert_eq!(pixel(1, y), [0, 0, 0, 0], "gap left of the monitor");
+            assert_eq!(pixel(2, y), red);
+            assert_eq!(pixel(3, y), red);
+            assert_eq!(pixel(4, y), blue);
+            assert_eq!(pixel(5, y), blue);
+        }
+    }
+
+    #[test]
+    fn contains_point_excludes_the_far_edges() {
+        let bounds = geometry(-100, 50, 200, 100);
+        assert!(contains_point(bounds, -100, 50));
+        assert!(contains_point(bounds, 99, 149));
+        assert!(!contains_point(bounds, 100, 50));
+        assert!(!contains_point(bounds, -100, 150));
+        assert!(!contains_point(bounds, -101, 50));
+    }
+
+    #[test]
+    fn hex_color_ignores_alpha() {
+        assert_eq!(hex_color([255, 8, 0, 0]), "#ff0800");
+        assert_eq!(hex_color([0x12, 0xab, 0xcd, 255]), "#12abcd");
+    }
+}
@@ -0,0 +1,193 @@
+// commands/self_test.rs
+// One diagnostic call for support: briefly toggles each native integration
+// (window level, opacity, focusable, stealth, click-through, shortcuts), puts
+// it back, and reports what actually works on this machine.
+
+use crate::commands::shortcuts::probe_global_shortcuts_now;
+use crate::commands::window::{
+    apply_window_level, content_protection_enabled, current_window_level, saved_opacity,
+    set_click_through, set_content_protection, set_focusable, set_opacity_command,
+    PassthroughRegions, WindowLevel,
+};
+use crate::types::WhisperSettings;
+use log::info;
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::{Manager, Window};
+
+// ============================================================================
+// Run Self Test Command
+// ============================================================================
+#[tauri::command]
+pub fn run_self_test_command(window: Window) -> Result<SelfTestReport, String> {
+    info!("🩺 Running native self-test...");
+    let mut results = Vec::new();
+
+    let level = current_window_level(&window);
+    let test_level = if level == WindowLevel::Normal {
+        WindowLevel::Floating
+    } else {
+        WindowLevel::Normal
+    };
+    results.push(run_feature(
+        "window_level",
+        || apply_window_level(&window, test_level),
+        || apply_window_level(&window, level),
+    ));
+
+    // The window shows at the saved opacity; nudge it away and back
+    let opacity = saved_opacity(&window.app_handle());
+    let test_opacity = if opacity > 0.5 {
+        opacity - 0.05
+    } else {
+        opacity + 0.05
+    };
+    results.push(run_feature(
+        "opacity",
+        || set_opacity_command(window.clone(), test_opacity),
+        || set_opacity_command(window.clone(), opacity),
+    ));
+
+    let settings = window.state::<Mutex<Option<WhisperSettings>>>();
+    let focusable = match settings.lock().as_deref() {
+        Ok(Some(settings)) => settings.focusable,
+        _ => true,
+    };
+    results.push(run_feature(
+        "focusable",
+        || set_focusable(&window, !focusable),
+        || set_focusable(&window, focusable),
+    ));
+
+    let protected = content_protection_enabled(&window);
+    results.push(run_feature(
+        "stealth",
+        || set_content_protection(&window, !protected),
+        || set_content_protection(&window, protected),
+    ));
+
+    // Pass-through regions keep flipping click-through themselves; toggling it
+    // underneath them would leave the poller out of sync
+    let regions = window.state::<PassthroughRegions>();
+    if regions.is_active() {
+        results.push(FeatureResult {
+            feature: "click_through".to_string(),
+            ok: false,
+            duration_ms: 0,
+            error: Some("Skipped while pass-through regions are active".to_string()),
+        });
+    } else {
+        let click_through = regions.whole_window();
+        results.push(run_feature(
+            "click_through",
+            || set_click_through(&window, !click_through),
+            || set_click_through(&window, click_through),
+        ));
+    }
+
+    results.push(run_feature(
+        "global_shortcuts",
+        || {
+            if probe_global_shortcuts_now(&window.app_handle()) {
+                Ok(())
+            } else {
+                Err("Global shortcuts can't be registered in this session".to_string())
+            }
+        },
+        || Ok(()),
+    ));
+
+    let passed = results.iter().filter(|result| result.ok).count();
+    info!("🩺 Self-test: {}/{} features work", passed, results.len());
+
+    Ok(SelfTestReport {
+        platform: std::env::consts::OS.to_string(),
+        tauri_version: tauri::VERSION.to_string(),
+        results,
+    })
+}
+
+// `restore` always runs, even if `toggle` failed halfway, so the window ends
+// up the way it was
+fn run_feature(
+    feature: &str,
+    toggle: impl FnOnce() -> Result<(), String>,
+    restore: impl FnOnce() -> Result<(), String>,
+) -> FeatureResult {
+    let started = Instant::now();
+    let toggled = toggle();
+    let restored = restore();
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    let error = match (toggled, restored) {
+        (Ok(()), Ok(())) => None,
+        (Err(e), Ok(())) => Some(e),
+        (Ok(()), Err(e)) => Some(format!("Failed to restore: {}", e)),
+        // Unsupported features fail the same way both times
+        (Err(e), Err(restore_error)) if e == restore_error => Some(e),
+        (Err(e), Err(restore_error)) => {
+            Some(format!("{} (and failed to restore: {})", e, restore_error))
+        }
+    };
+
+    FeatureResult {
+        feature: feature.to_string(),
+        ok: error.is_none(),
+        duration_ms,
+        error,
+    }
+}
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SelfTestReport {
+    pub platform: String,
+    pub tauri_version: String,
+    pub results: Vec<FeatureResult>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FeatureResult {
+    pub feature: String,
+    pub ok: bool,
+    pub duration_ms: u64,
+    pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn features_are_restored_even_when_toggling_fails() {
+        let enabled = Cell::new(false);
+
+        let result = run_feature(
+            "stealth",
+            || {
+                enabled.set(true);
+                Err("Failed halfway".to_string())
+            },
+            || {
+                enabled.set(false);
+                Ok(())
+            },
+        );
+        assert!(!result.ok);
+        assert_eq!(result.error.as_deref(), Some("Failed halfway"));
+        assert!(!enabled.get());
+
+        let unsupported = || Err("Not supported".to_string());
+        let result = run_feature("opacity", unsupported, unsupported);
+        assert_eq!(result.error.as_deref(), Some("Not supported"));
+
+        let result = run_feature("focusable", || Ok(()), || Err("Stuck".to_string()));
+        assert_eq!(result.error.as_deref(), Some("Failed to restore: Stuck"));
+
+        assert!(run_feature("window_level", || Ok(()), || Ok(())).ok);
+    }
+}
@@ -0,0 +1,154 @@
+// commands/session.rs
+// Remembers the last provider conversation so the overlay can reopen it on
+// the next launch instead of starting a fresh chat.
+
+use crate::commands::page::sync_window_title;
+use crate::commands::settings::{update_settings, update_settings_debounced};
+use crate::commands::whispr::{is_dashboard_url, launch_whispr_mode_command, provider_for_url};
+use crate::limits::{check_size, MAX_PAGE_REPORT_BYTES};
+use crate::types::WhisperSettings;
+use log::{error, info, warn};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State, Window};
+use url::Url;
+
+// Reports the page URL on load and on every history change - providers are
+// single-page apps, so opening a conversation doesn't trigger a page load
+const URL_REPORTER_SCRIPT: &str = r#"(function () {
+  if (window.__whisprUrlReporter) return;
+  window.__whisprUrlReporter = true;
+
+  var last = null;
+  function report() {
+    if (!window.__TAURI_INVOKE__ || window.location.href === last) return;
+    last = window.location.href;
+    window.__TAURI_INVOKE__('report_provider_url_command', { url: last }).catch(function () {});
+  }
+
+  ['pushState', 'replaceState'].forEach(function (name) {
+    var original = history[name];
+    history[name] = function () {
+      var result = original.apply(this, arguments);
+      report();
+      return result;
+    };
+  });
+  window.addEventListener('popstate', report);
+  report();
+})()"#;
+
+// ============================================================================
+// Report Provider URL Command
+// ============================================================================
+// Called by URL_REPORTER_SCRIPT. Anything outside the provider allowlist is
+// ignored.
+#[tauri::command]
+pub fn report_provider_url_command(
+    app_handle: AppHandle,
+    state: State<Mutex<Option<WhisperSettings>>>,
+    url: String,
+) -> Result<(), String> {
+    check_size("URL", url.len(), MAX_PAGE_REPORT_BYTES)?;
+    let Some(url) = valid_provider_url(&url) else {
+        return Ok(());
+    };
+
+    let unchanged = state
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|settings| settings.last_provider_url.as_deref() == Some(url.as_str()));
+    if unchanged {
+        return Ok(());
+    }
+
+    // Navigation can be chatty, so let the saver coalesce these writes
+    update_settings_debounced(&app_handle, &state, |settings| {
+        settings.last_provider_url = Some(url.to_string())
+    })?;
+    sync_window_title(&app_handle);
+    Ok(())
+}
+
+// ============================================================================
+// Clear Last Session Command
+// ============================================================================
+#[tauri::command]
+pub fn clear_last_session_command(
+    app_handle: AppHandle,
+    state: State<Mutex<Option<WhisperSettings>>>,
+) -> Result<(), String> {
+    update_settings(&app_handle, &state, |settings| {
+        settings.last_provider_url = None
+    })?;
+    info!("🧹 Last session cleared");
+    Ok(())
+}
+
+// ============================================================================
+// Session Restore
+// ============================================================================
+// Called from setup(); the navigation itself waits for the dashboard to load
+// (see restore_last_session) so it doesn't race the initial page.
+pub fn queue_session_restore(app_handle: &AppHandle, last_provider_url: Option<&str>) {
+    let Some(last_provider_url) = last_provider_url else {
+        return;
+    };
+
+    match valid_provider_url(last_provider_url) {
+        Some(url) => {
+            info!("🔁 Restoring last session: {}", url);
+            *app_handle.state::<SessionRestore>().pending.lock().unwrap() = Some(url);
+        }
+        None => warn!(
+            "⚠️  Not restoring last session, not a provider URL: {}",
+            last_provider_url
+        ),
+    }
+}
+
+// Called from the page-load hook in main.rs for every new document
+pub fn restore_last_session(window: &Window, url: &str) {
+    if !Url::parse(url).is_ok_and(|url| is_dashboard_url(&url)) {
+        return;
+    }
+    let Some(url) = window
+        .state::<SessionRestore>()
+        .pending
+        .lock()
+        .unwrap()
+        .take()
+    else {
+        return;
+    };
+
+    if let Err(e) = launch_whispr_mode_command(window.app_handle(), url.to_string()) {
+        error!("❌ Failed to restore last session: {}", e);
+    }
+}
+
+pub fn install_url_reporter(window: &Window, url: &str) {
+    if !Url::parse(url).is_ok_and(|url| provider_for_url(&url).is_some()) {
+        return;
+    }
+
+    if let Err(e) = window.eval(URL_REPORTER_SCRIPT) {
+        error!("❌ Failed to install URL reporter: {}", e);
+    }
+}
+
+// https URLs on a known provider's host only
+fn valid_provider_url(url: &str) -> Option<Url> {
+    let url = Url::parse(url).ok()?;
+    (url.scheme() == "https" && provider_for_url(&url).is_some()).then_some(url)
+}
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+// Managed state: the URL to reopen once the dashboard has loaded
+#[derive(Default)]
+pub struct SessionRestore {
+    pending: Mutex<Option<Url>>,
+}
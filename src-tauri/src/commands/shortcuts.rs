@@ -1,10 +1,23 @@
 // src-tauri/src/commands/shortcuts.rs
 // UPDATED: Using log crate for proper logging
 
-use crate::types::WhisperSettings;
-use log::{debug, error, info};
+use crate::commands::settings::{try_update_settings, update_settings};
+use crate::commands::templates::run_template;
+use crate::commands::whispr::{navigate_back_command, navigate_to_dashboard_command};
+use crate::commands::window::{
+    cycle_window_slot_command, decrease_opacity_command, increase_opacity_command,
+    move_to_next_monitor_command, move_window_command, panic_hide_command, require_main_window,
+    saved_opacity, toggle_always_on_top_command, toggle_window_visibility_command,
+};
+use crate::limits::{check_size, MAX_PAGE_REPORT_BYTES, MAX_SETTINGS_BYTES};
+use crate::state::settings::load_settings;
+use crate::types::{CustomShortcut, ShortcutEntry, WhisperSettings};
+use log::{debug, error, info, warn};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Mutex;
-use tauri::{AppHandle, GlobalShortcutManager, State};
+use std::time::Duration;
+use tauri::{AppHandle, GlobalShortcutManager, Manager, State, Window};
 
 // Shortcut Parser
 pub fn parse_shortcut(verbose: &str, is_mac: bool) -> Option<String> {
@@ -35,6 +48,15 @@ pub fn parse_shortcut(verbose: &str, is_mac: bool) -> Option<String> {
             "esc" | "escape" => "Escape".to_string(),
             "space" => "Space".to_string(),
             "tab" => "Tab".to_string(),
+            "pageup" | "pgup" => "PageUp".to_string(),
+            "pagedown" | "pgdn" => "PageDown".to_string(),
+            "home" => "Home".to_string(),
+            "end" => "End".to_string(),
+            "insert" | "ins" => "Insert".to_string(),
+            "delete" | "del" => "Delete".to_string(),
+            "backspace" | "⌫" => "Backspace".to_string(),
+            lower if function_key(lower).is_some() => lower.to_uppercase(),
+            lower if numpad_key(lower).is_some() => numpad_key(lower).unwrap_or_default(),
             _ => {
                 if part.len() == 1 {
                     part.to_uppercase()
@@ -52,80 +74,656 @@ pub fn parse_shortcut(verbose: &str, is_mac: bool) -> Option<String> {
     if parts.is_empty() {
         None
     } else {
-        Some(parts.join("+"))
+        Some(canonical_chord(parts))
     }
 }
 
+// "f12" -> "F12", for F1 through F24
+fn function_key(part: &str) -> Option<String> {
+    let n: u8 = part.strip_prefix(['f', 'F'])?.parse().ok()?;
+    (1..=24).contains(&n).then(|| format!("F{}", n))
+}
+
+// "numpad5" / "num5" -> "Num5", spelled the way tao parses them
+fn numpad_key(part: &str) -> Option<String> {
+    let lower = part.to_lowercase();
+    let key = lower
+        .strip_prefix("numpad")
+        .or_else(|| lower.strip_prefix("num"))?;
+    let name = match key {
+        "add" | "plus" => "Add",
+        // tao only knows the misspelled name
+        "subtract" | "minus" => "Substract",
+        "divide" => "Divide",
+        "enter" => "Enter",
+        digit if digit.len() == 1 && digit.as_bytes()[0].is_ascii_digit() => digit,
+        _ => return None,
+    };
+    Some(format!("Num{}", name))
+}
+
+// Modifiers in MODIFIER_KEYS order with repeats dropped, then the key, so
+// "Shift+Ctrl+T" and "Ctrl+Shift+T" compare equal
+fn canonical_chord(parts: Vec<String>) -> String {
+    let (modifiers, keys): (Vec<String>, Vec<String>) = parts
+        .into_iter()
+        .partition(|part| MODIFIER_KEYS.contains(&part.as_str()));
+
+    MODIFIER_KEYS
+        .iter()
+        .filter(|modifier| modifiers.iter().any(|m| m == *modifier))
+        .map(|modifier| modifier.to_string())
+        .chain(keys)
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
 #[tauri::command]
 pub fn register_shortcuts_command(
     app: AppHandle,
-    settings: State<Mutex<WhisperSettings>>,
-) -> Result<bool, String> {
+    settings: State<Mutex<Option<WhisperSettings>>>,
+) -> Result<RegistrationResult, String> {
     info!("⌨️  Registering shortcuts...");
 
-    let settings = settings.lock().map_err(|e| e.to_string())?;
+    let mut settings = settings.lock().map_err(|e| e.to_string())?;
+    if settings.is_none() {
+        *settings = Some(load_settings(&app)?);
+    }
+
+    register_shortcuts(&app, settings.as_ref().unwrap())
+}
+
+// Replaces all registered global shortcuts with the ones in `settings`. A
+// shortcut the OS refuses doesn't stop the others; it's listed in `failed`.
+pub fn register_shortcuts(
+    app: &AppHandle,
+    settings: &WhisperSettings,
+) -> Result<RegistrationResult, String> {
     let is_mac = cfg!(target_os = "macos");
 
+    if let Err(e) = check_shortcut_conflicts(&settings.shortcuts, is_mac) {
+        warn!("⚠️  {}", e);
+    }
+
     let mut shortcut_manager = app.global_shortcut_manager();
 
     shortcut_manager
         .unregister_all()
         .map_err(|e| format!("Failed to unregister shortcuts: {}", e))?;
 
-    let mut registered = 0;
-    let mut failed = 0;
+    let mut results = Vec::new();
+    let mut sequences = Vec::new();
 
     for (key, shortcut_entry) in &settings.shortcuts {
-        let platform = if is_mac { "mac" } else { "windows" };
-
-        // Get shortcut string - check custom first, then default
-        let shortcut_str = if let Some(custom) = &shortcut_entry.custom_shortcut {
-            if platform == "mac" {
-                custom
-                    .mac
-                    .as_deref()
-                    .unwrap_or(&shortcut_entry.default_shortcut.mac)
-            } else {
-                custom
-                    .windows
-                    .as_deref()
-                    .unwrap_or(&shortcut_entry.default_shortcut.windows)
-            }
-        } else {
-            if platform == "mac" {
-                &shortcut_entry.default_shortcut.mac
-            } else {
-                &shortcut_entry.default_shortcut.windows
-            }
-        };
+        let shortcut_str = effective_shortcut(shortcut_entry, is_mac);
 
         if shortcut_str.is_empty() {
             continue;
         }
 
-        if let Some(parsed) = parse_shortcut(shortcut_str, is_mac) {
+        if let Some(chords) = parse_shortcut_sequence(shortcut_str, is_mac) {
+            if chords.len() > 1 {
+                sequences.push((chords, key.clone()));
+                continue;
+            }
+
+            let parsed = chords.into_iter().next().unwrap_or_default();
             let key_clone = key.clone();
+            let (app_clone, accelerator) = (app.clone(), parsed.clone());
 
-            match shortcut_manager.register(&parsed, move || {
+            let result = shortcut_manager.register(&parsed, move || {
+                if suppressed_for_text_input(&app_clone, &accelerator)
+                    || press_chord(&app_clone, &accelerator)
+                {
+                    return;
+                }
                 debug!("Shortcut triggered: {}", key_clone);
-            }) {
-                Ok(_) => {
-                    debug!("Registered shortcut: {} -> {}", key, parsed);
-                    registered += 1;
+                if let Err(e) = run_shortcut_action(&app_clone, &key_clone, false) {
+                    error!("❌ Shortcut '{}' failed: {}", key_clone, e);
                 }
-                Err(e) => {
-                    error!("Failed to register shortcut {}: {}", key, e);
-                    failed += 1;
+            });
+            match &result {
+                Ok(_) => debug!("Registered shortcut: {} -> {}", key, parsed),
+                Err(e) => error!("Failed to register shortcut {}: {}", key, e),
+            }
+            results.push(RegisteredShortcut {
+                command_key: key.clone(),
+                warning: system_shortcut_warning(&parsed, is_mac),
+                accelerator: parsed,
+                registered: result.is_ok(),
+                error: result.err().map(|e| e.to_string()),
+            });
+        }
+    }
+
+    let mut taken: Vec<String> = results
+        .iter()
+        .map(|result| result.accelerator.clone())
+        .collect();
+
+    // Sequences: only the first chord is registered up front (once, however
+    // many sequences start with it), see press_chord
+    sequences.sort();
+    for (chords, key) in &sequences {
+        let first = chords[0].clone();
+        let result = if taken.contains(&first) {
+            warn!("⚠️  {} is taken, so '{}' can't start with it", first, key);
+            Err(format!("{} is already used by another shortcut", first))
+        } else {
+            taken.push(first.clone());
+            let (app_clone, chord) = (app.clone(), first.clone());
+            let result = shortcut_manager.register(&first, move || {
+                if !suppressed_for_text_input(&app_clone, &chord) {
+                    press_chord(&app_clone, &chord);
                 }
+            });
+            if let Err(e) = &result {
+                error!("Failed to register shortcut {}: {}", key, e);
+            }
+            result.map_err(|e| e.to_string())
+        };
+        results.push(RegisteredShortcut {
+            command_key: key.clone(),
+            accelerator: chords.join(" "),
+            registered: result.is_ok(),
+            warning: system_shortcut_warning(&first, is_mac),
+            error: result.err(),
+        });
+    }
+
+    // The same moves with Shift added go FAST_MOVE_MULTIPLIER times as far,
+    // unless another shortcut already uses that combination
+    for (key, shortcut_entry) in &settings.shortcuts {
+        if !matches!(shortcut_action(key), ShortcutAction::Move(..)) {
+            continue;
+        }
+        let Some(fast) =
+            parse_shortcut_sequence(effective_shortcut(shortcut_entry, is_mac), is_mac)
+                .filter(|chords| chords.len() == 1)
+                .and_then(|chords| with_shift(&chords[0]))
+        else {
+            continue;
+        };
+        if taken.contains(&fast) {
+            debug!("No fast move for {}: {} is taken", key, fast);
+            continue;
+        }
+
+        taken.push(fast.clone());
+
+        let (key_clone, app_clone, accelerator) = (key.clone(), app.clone(), fast.clone());
+        let result = shortcut_manager.register(&fast, move || {
+            if suppressed_for_text_input(&app_clone, &accelerator)
+                || press_chord(&app_clone, &accelerator)
+            {
+                return;
             }
+            if let Err(e) = run_shortcut_action(&app_clone, &key_clone, true) {
+                error!("❌ Shortcut '{}' failed: {}", key_clone, e);
+            }
+        });
+        if let Err(e) = &result {
+            error!("Failed to register fast move {}: {}", key, e);
         }
+        results.push(RegisteredShortcut {
+            command_key: key.clone(),
+            warning: system_shortcut_warning(&fast, is_mac),
+            accelerator: fast,
+            registered: result.is_ok(),
+            error: result.err().map(|e| e.to_string()),
+        });
     }
 
+    // Built-in shortcuts that aren't in the settings
+    for (accelerator, key) in FIXED_SHORTCUTS {
+        if taken.iter().any(|used| used == accelerator) {
+            warn!("⚠️  {} is taken, no shortcut for {}", accelerator, key);
+            continue;
+        }
+        taken.push(accelerator.to_string());
+
+        let app_clone = app.clone();
+        let result = shortcut_manager.register(accelerator, move || {
+            if press_chord(&app_clone, accelerator) {
+                return;
+            }
+            if let Err(e) = run_shortcut_action(&app_clone, key, false) {
+                error!("❌ Shortcut '{}' failed: {}", key, e);
+            }
+        });
+        if let Err(e) = &result {
+            error!("Failed to register shortcut {}: {}", key, e);
+        }
+        results.push(RegisteredShortcut {
+            command_key: key.to_string(),
+            accelerator: accelerator.to_string(),
+            registered: result.is_ok(),
+            warning: system_shortcut_warning(accelerator, is_mac),
+            error: result.err().map(|e| e.to_string()),
+        });
+    }
+
+    let registered = results.iter().filter(|result| result.registered).count();
     info!(
         "✅ Shortcuts registered: {} succeeded, {} failed",
-        registered, failed
+        registered,
+        results.len() - registered
     );
-    Ok(true)
+    let outcome = RegistrationResult::from_results(&results);
+    if let Some(state) = app.try_state::<RegisteredShortcuts>() {
+        state.set(results);
+    }
+    if let Some(state) = app.try_state::<ChordSequences>() {
+        state.reset(sequences, taken);
+    }
+    Ok(outcome)
+}
+
+// ============================================================================
+// Chord Sequences
+// ============================================================================
+// "Ctrl+K Ctrl+S": pressing the first chord starts the sequence, and the chords
+// that can follow are registered until it completes, something else is
+// pressed, or SEQUENCE_TIMEOUT passes. Accelerators that are registered anyway
+// (e.g. Ctrl+S for screenshot) check press_chord before doing their own thing.
+const SEQUENCE_TIMEOUT: Duration = Duration::from_millis(1500);
+
+// The chords of a shortcut, split on whitespace ("Ctrl + K Ctrl + S" ->
+// ["Ctrl+K", "Ctrl+S"]); a single chord is the parse_shortcut result
+pub fn parse_shortcut_sequence(verbose: &str, is_mac: bool) -> Option<Vec<String>> {
+    // Spaces around '+' belong to the chord, the others separate chords
+    let compact = verbose
+        .split('+')
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join("+");
+
+    compact
+        .split_whitespace()
+        .map(|chord| parse_shortcut(chord, is_mac))
+        .collect::<Option<Vec<String>>>()
+        .filter(|chords| !chords.is_empty())
+}
+
+// Called by every registered accelerator; true if it was part of a sequence
+// (so the accelerator's own action shouldn't run)
+fn press_chord(app: &AppHandle, chord: &str) -> bool {
+    let Some(sequences) = app.try_state::<ChordSequences>() else {
+        return false;
+    };
+    let mut state = sequences.state.lock().unwrap();
+
+    let pending = !state.typed.is_empty();
+    let mut typed = state.typed.clone();
+    typed.push(chord.to_string());
+    let mut step = sequence_step(&state.sequences, &typed);
+    if step == SequenceStep::NoMatch && pending {
+        // Not a continuation, but it may start another sequence
+        typed = vec![chord.to_string()];
+        step = sequence_step(&state.sequences, &typed);
+    }
+    if step == SequenceStep::NoMatch && !pending {
+        return false;
+    }
+
+    state.generation += 1;
+    let previous = std::mem::take(&mut state.temporary);
+    match step {
+        SequenceStep::Complete(key) => {
+            state.typed.clear();
+            drop(state);
+            swap_temporary_chords(app, previous, Vec::new());
+
+            debug!("Shortcut sequence triggered: {}", key);
+            if let Err(e) = run_shortcut_action(app, &key, false) {
+                error!("❌ Shortcut '{}' failed: {}", key, e);
+            }
+            true
+        }
+        SequenceStep::Partial(next) => {
+            let next: Vec<String> = next
+                .into_iter()
+                .filter(|chord| !state.registered.contains(chord))
+                .collect();
+            state.typed = typed;
+            state.temporary = next.clone();
+            let generation = state.generation;
+            drop(state);
+
+            swap_temporary_chords(app, previous, next);
+            expire_sequence(app, generation);
+            true
+        }
+        SequenceStep::NoMatch => {
+            state.typed.clear();
+            drop(state);
+            swap_temporary_chords(app, previous, Vec::new());
+            false
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum SequenceStep {
+    Complete(String),
+    // The chords that can come next
+    Partial(Vec<String>),
+    NoMatch,
+}
+
+fn sequence_step(sequences: &[(Vec<String>, String)], typed: &[String]) -> SequenceStep {
+    let matching: Vec<&(Vec<String>, String)> = sequences
+        .iter()
+        .filter(|(chords, _)| chords.starts_with(typed))
+        .collect();
+
+    if let Some((_, key)) = matching
+        .iter()
+        .find(|(chords, _)| chords.len() == typed.len())
+    {
+        return SequenceStep::Complete(key.clone());
+    }
+    if matching.is_empty() {
+        return SequenceStep::NoMatch;
+    }
+
+    let mut next: Vec<String> = matching
+        .iter()
+        .map(|(chords, _)| chords[typed.len()].clone())
+        .collect();
+    next.sort_unstable();
+    next.dedup();
+    SequenceStep::Partial(next)
+}
+
+// Registering from inside a shortcut handler would wait on the event loop
+// that's running the handler, so this happens on a thread of its own
+fn swap_temporary_chords(app: &AppHandle, remove: Vec<String>, add: Vec<String>) {
+    if remove.is_empty() && add.is_empty() {
+        return;
+    }
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let mut shortcut_manager = app.global_shortcut_manager();
+        for chord in remove.iter().filter(|chord| !add.contains(chord)) {
+            let _ = GlobalShortcutManager::unregister(&mut shortcut_manager, chord);
+        }
+        for chord in add.iter().filter(|chord| !remove.contains(chord)) {
+            let (app_clone, accelerator) = (app.clone(), chord.clone());
+            if let Err(e) = shortcut_manager.register(chord, move || {
+                press_chord(&app_clone, &accelerator);
+            }) {
+                warn!("⚠️  Failed to register sequence chord {}: {}", chord, e);
+            }
+        }
+    });
+}
+
+fn expire_sequence(app: &AppHandle, generation: u64) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(SEQUENCE_TIMEOUT);
+
+        let sequences = app.state::<ChordSequences>();
+        let mut state = sequences.state.lock().unwrap();
+        if state.generation != generation {
+            return;
+        }
+        state.generation += 1;
+        state.typed.clear();
+        let temporary = std::mem::take(&mut state.temporary);
+        drop(state);
+
+        debug!("Shortcut sequence timed out");
+        swap_temporary_chords(&app, temporary, Vec::new());
+    });
+}
+
+// ============================================================================
+// Get Registered Shortcuts
+// ============================================================================
+// What the last registration actually got from the OS, so the settings UI can
+// flag shortcuts that failed (e.g. reserved by the system or another app)
+#[tauri::command]
+pub fn get_registered_shortcuts_command(
+    state: State<RegisteredShortcuts>,
+) -> Result<Vec<RegisteredShortcut>, String> {
+    Ok(state.list())
+}
+
+// ============================================================================
+// Shortcut Recording
+// ============================================================================
+// "Press keys to set": the next key combination pressed in the calling window
+// arrives as a "shortcut-recorded" event - the normalized accelerator, or null
+// if nothing usable was pressed before RECORDING_TIMEOUT. Global shortcuts are
+// suspended meanwhile, so the current bindings can be recorded too.
+const RECORDING_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[tauri::command]
+pub fn start_shortcut_recording_command(
+    app: AppHandle,
+    window: Window,
+    recording: State<ShortcutRecording>,
+) -> Result<(), String> {
+    let id = recording.start();
+    let is_mac = cfg!(target_os = "macos");
+
+    app.global_shortcut_manager()
+        .unregister_all()
+        .map_err(|e| format!("Failed to suspend shortcuts: {}", e))?;
+
+    if let Err(e) = window.eval(&recorder_script(id, is_mac)) {
+        recording.finish(id);
+        restore_shortcuts(&app);
+        return Err(format!("Failed to start recording: {}", e));
+    }
+    info!("🎙️  Recording a shortcut...");
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(RECORDING_TIMEOUT);
+        if app.state::<ShortcutRecording>().finish(id) {
+            info!("⏱️  Shortcut recording timed out");
+            finish_recording(&app, None);
+        }
+    });
+    Ok(())
+}
+
+// Called by the script injected in start_shortcut_recording_command
+#[tauri::command]
+pub fn report_shortcut_keypress_command(
+    app: AppHandle,
+    recording: State<ShortcutRecording>,
+    id: u64,
+    keys: String,
+) -> Result<(), String> {
+    check_size("Keys", keys.len(), MAX_PAGE_REPORT_BYTES)?;
+    if !recording.finish(id) {
+        return Ok(());
+    }
+
+    let accelerator = match validate_accelerator(&keys, cfg!(target_os = "macos")) {
+        Ok(accelerator) => Some(accelerator),
+        Err(e) => {
+            warn!("⚠️  Recorded keys can't be used as a shortcut: {}", e);
+            None
+        }
+    };
+    finish_recording(&app, accelerator);
+    Ok(())
+}
+
+fn finish_recording(app: &AppHandle, accelerator: Option<String>) {
+    restore_shortcuts(app);
+    if let Err(e) = app.emit_all("shortcut-recorded", accelerator) {
+        error!("❌ Failed to emit shortcut-recorded: {}", e);
+    }
+}
+
+fn restore_shortcuts(app: &AppHandle) {
+    let state = app.state::<Mutex<Option<WhisperSettings>>>();
+    let settings = state.lock().unwrap().clone();
+    let result = match settings {
+        Some(settings) => register_shortcuts(app, &settings),
+        None => load_settings(app).and_then(|settings| register_shortcuts(app, &settings)),
+    };
+    if let Err(e) = result {
+        error!("❌ Failed to restore shortcuts after recording: {}", e);
+    }
+}
+
+// A one-shot capturing keydown listener. Keys are named from e.code so Alt and
+// Shift don't change them; lone modifier presses keep it waiting. The Windows
+// key is ignored off the mac, where "Cmd" would be read as Ctrl.
+fn recorder_script(id: u64, is_mac: bool) -> String {
+    format!(
+        r#"(function () {{
+  var done = false;
+  function stop() {{
+    done = true;
+    window.removeEventListener('keydown', onKey, true);
+  }}
+  function keyName(e) {{
+    var code = e.code || '', m;
+    if ((m = /^(?:Key|Digit|Arrow)(\w+)$/.exec(code))) return m[1];
+    if ((m = /^Numpad(\w+)$/.exec(code))) return 'Num' + m[1];
+    if (e.key && e.key.length === 1) return e.key.toUpperCase();
+    return code;
+  }}
+  function onKey(e) {{
+    if (done || ['Control', 'Shift', 'Alt', 'Meta'].indexOf(e.key) !== -1) return;
+    if (e.metaKey && !{is_mac}) return;
+    e.preventDefault();
+    e.stopPropagation();
+    stop();
+    var keys = [];
+    if (e.ctrlKey) keys.push('Ctrl');
+    if (e.altKey) keys.push('Alt');
+    if (e.shiftKey) keys.push('Shift');
+    if (e.metaKey) keys.push('Cmd');
+    keys.push(keyName(e));
+    window.__TAURI_INVOKE__ && window.__TAURI_INVOKE__('report_shortcut_keypress_command',
+      {{ id: {id}, keys: keys.join('+') }}).catch(function () {{}});
+  }}
+  window.addEventListener('keydown', onKey, true);
+  setTimeout(stop, {timeout});
+}})()"#,
+        id = id,
+        is_mac = is_mac,
+        timeout = RECORDING_TIMEOUT.as_millis()
+    )
+}
+
+// ============================================================================
+// Shortcut Actions
+// ============================================================================
+// Window movement, hide/show, home, back, window slots and prompt templates
+// ("template:{name}" keys) are handled right here; everything else (and any
+// key this build doesn't know) goes to the frontend as "shortcut://{key}".
+const FAST_MOVE_MULTIPLIER: i32 = 5;
+
+// (accelerator, key) pairs registered alongside the ones from the settings
+const FIXED_SHORTCUTS: &[(&str, &str)] = &[("Ctrl+Alt+W", "cycle-window-slots")];
+
+const TEMPLATE_SHORTCUT_PREFIX: &str = "template:";
+
+fn run_shortcut_action(app: &AppHandle, key: &str, fast: bool) -> Result<(), String> {
+    let window = require_main_window(app)?;
+
+    match shortcut_action(key) {
+        ShortcutAction::Move(direction_x, direction_y) => {
+            let step = if fast {
+                move_step_px(app).saturating_mul(FAST_MOVE_MULTIPLIER)
+            } else {
+                move_step_px(app)
+            };
+            move_window_command(window, direction_x * step, direction_y * step)
+        }
+        ShortcutAction::ToggleVisibility => {
+            toggle_window_visibility_command(window, saved_opacity(app))
+        }
+        ShortcutAction::NextMonitor => move_to_next_monitor_command(window),
+        ShortcutAction::PanicHide => panic_hide_command(window),
+        ShortcutAction::OpacityUp => {
+            increase_opacity_command(app.clone(), window, app.state()).map(|_| ())
+        }
+        ShortcutAction::OpacityDown => {
+            decrease_opacity_command(app.clone(), window, app.state()).map(|_| ())
+        }
+        ShortcutAction::ToggleAlwaysOnTop => {
+            toggle_always_on_top_command(app.clone(), window, app.state()).map(|_| ())
+        }
+        ShortcutAction::NavigateBack => navigate_back_command(app.clone(), app.state()),
+        ShortcutAction::Home => navigate_to_dashboard_command(app.clone()),
+        ShortcutAction::CycleWindowSlots => {
+            cycle_window_slot_command(app.clone(), window, app.state()).map(|_| ())
+        }
+        ShortcutAction::RunTemplate(name) => run_template(app, name),
+        ShortcutAction::Emit => window
+            .emit(&format!("shortcut://{}", key), ())
+            .map_err(|e| format!("Failed to emit shortcut event: {}", e)),
+    }
+}
+
+// Move directions are -1, 0 or 1 on each axis
+fn shortcut_action(key: &str) -> ShortcutAction<'_> {
+    if let Some(name) = key.strip_prefix(TEMPLATE_SHORTCUT_PREFIX) {
+        return ShortcutAction::RunTemplate(name);
+    }
+
+    match key {
+        "move-up" => ShortcutAction::Move(0, -1),
+        "move-down" => ShortcutAction::Move(0, 1),
+        "move-left" => ShortcutAction::Move(-1, 0),
+        "move-right" => ShortcutAction::Move(1, 0),
+        "next-monitor" => ShortcutAction::NextMonitor,
+        "hide-show" => ShortcutAction::ToggleVisibility,
+        "panic" => ShortcutAction::PanicHide,
+        "opacity-up" => ShortcutAction::OpacityUp,
+        "opacity-down" => ShortcutAction::OpacityDown,
+        "always-on-top" => ShortcutAction::ToggleAlwaysOnTop,
+        "back" => ShortcutAction::NavigateBack,
+        "home" => ShortcutAction::Home,
+        "cycle-window-slots" => ShortcutAction::CycleWindowSlots,
+        _ => ShortcutAction::Emit,
+    }
+}
+
+// move_step from the settings in memory (the default before they're loaded)
+pub fn move_step_px(app: &AppHandle) -> i32 {
+    let state = app.state::<Mutex<Option<WhisperSettings>>>();
+    let step = match state.lock().as_deref() {
+        Ok(Some(settings)) => settings.move_step,
+        _ => WhisperSettings::default().move_step,
+    };
+    i32::try_from(step).unwrap_or(i32::MAX)
+}
+
+// "Ctrl+Up" -> "Ctrl+Shift+Up"; None if it already has Shift
+fn with_shift(accelerator: &str) -> Option<String> {
+    let mut parts: Vec<String> = accelerator.split('+').map(str::to_string).collect();
+    if parts.iter().any(|part| part == "Shift") {
+        return None;
+    }
+    parts.push("Shift".to_string());
+    Some(canonical_chord(parts))
+}
+
+// The accelerator in use on a platform - custom first, then default
+pub fn effective_shortcut(entry: &ShortcutEntry, is_mac: bool) -> &str {
+    let custom = entry.custom_shortcut.as_ref();
+    if is_mac {
+        custom
+            .and_then(|custom| custom.mac.as_deref())
+            .unwrap_or(&entry.default_shortcut.mac)
+    } else {
+        custom
+            .and_then(|custom| custom.windows.as_deref())
+            .unwrap_or(&entry.default_shortcut.windows)
+    }
 }
 
 #[tauri::command]
@@ -135,63 +733,1195 @@ pub fn unregister_shortcuts_command(app: AppHandle) -> Result<bool, String> {
     app.global_shortcut_manager()
         .unregister_all()
         .map_err(|e| format!("Failed to unregister shortcuts: {}", e))?;
+    app.state::<RegisteredShortcuts>().set(Vec::new());
 
     info!("✅ All shortcuts unregistered");
     Ok(true)
 }
 
+// Rejects accelerators that could never be registered ("Ctrl+", "Foo+Bar",
+// "Ctrl+Shift") so the settings UI can show the error inline
 #[tauri::command]
 pub fn update_shortcut_command(
+    app: AppHandle,
     command_key: String,
     shortcut: String,
     platform: String,
-    settings: State<Mutex<WhisperSettings>>,
+    settings: State<Mutex<Option<WhisperSettings>>>,
 ) -> Result<bool, String> {
     info!(
         "🔧 Updating shortcut '{}' to '{}' on {}",
         command_key, shortcut, platform
     );
 
-    let mut settings = settings.lock().map_err(|e| e.to_string())?;
+    validate_accelerator(&shortcut, platform == "mac")?;
+
+    try_update_settings(&app, &settings, |settings| {
+        let Some(shortcut_entry) = settings.shortcuts.get_mut(&command_key) else {
+            error!("❌ Shortcut command '{}' not found", command_key);
+            return Err(format!("Shortcut command '{}' not found", command_key));
+        };
 
-    if let Some(shortcut_entry) = settings.shortcuts.get_mut(&command_key) {
-        let mut custom = shortcut_entry.custom_shortcut.clone().unwrap_or_else(|| {
-            crate::types::CustomShortcut {
+        let custom = shortcut_entry
+            .custom_shortcut
+            .get_or_insert(CustomShortcut {
                 mac: None,
                 windows: None,
-            }
-        });
-
+            });
         if platform == "mac" {
             custom.mac = Some(shortcut.clone());
         } else {
             custom.windows = Some(shortcut.clone());
         }
+        Ok(())
+    })?;
 
-        shortcut_entry.custom_shortcut = Some(custom);
-        info!("✅ Shortcut '{}' updated successfully", command_key);
-        Ok(true)
-    } else {
-        error!("❌ Shortcut command '{}' not found", command_key);
-        Err(format!("Shortcut command '{}' not found", command_key))
-    }
+    info!("✅ Shortcut '{}' updated successfully", command_key);
+    Ok(true)
 }
 
 #[tauri::command]
 pub fn reset_shortcut_command(
+    app: AppHandle,
     command_key: String,
-    settings: State<Mutex<WhisperSettings>>,
+    settings: State<Mutex<Option<WhisperSettings>>>,
 ) -> Result<bool, String> {
     info!("🔄 Resetting shortcut '{}'", command_key);
 
-    let mut settings = settings.lock().map_err(|e| e.to_string())?;
-
-    if let Some(shortcut_entry) = settings.shortcuts.get_mut(&command_key) {
+    try_update_settings(&app, &settings, |settings| {
+        let Some(shortcut_entry) = settings.shortcuts.get_mut(&command_key) else {
+            error!("❌ Shortcut command '{}' not found", command_key);
+            return Err(format!("Shortcut command '{}' not found", command_key));
+        };
         shortcut_entry.custom_shortcut = None;
-        info!("✅ Shortcut '{}' reset to default", command_key);
-        Ok(true)
+        Ok(())
+    })?;
+
+    info!("✅ Shortcut '{}' reset to default", command_key);
+    Ok(true)
+}
+
+// ============================================================================
+// Accelerator Validation
+// ============================================================================
+// In the order parse_shortcut writes them
+const MODIFIER_KEYS: [&str; 4] = ["Ctrl", "Alt", "Shift", "Cmd"];
+
+// Multi-character key names as parse_shortcut spells them
+const NAMED_KEYS: [&str; 15] = [
+    "Up",
+    "Down",
+    "Left",
+    "Right",
+    "Enter",
+    "Escape",
+    "Space",
+    "Tab",
+    "Home",
+    "End",
+    "PageUp",
+    "PageDown",
+    "Insert",
+    "Delete",
+    "Backspace",
+];
+
+// The parsed accelerator, if each of its chords is any number of modifiers
+// plus exactly one key that can actually be registered
+pub fn validate_accelerator(shortcut: &str, is_mac: bool) -> Result<String, String> {
+    let invalid = || format!("'{}' is not a valid accelerator", shortcut);
+
+    let chords = parse_shortcut_sequence(shortcut, is_mac).ok_or_else(invalid)?;
+    for chord in &chords {
+        let keys: Vec<&str> = chord
+            .split('+')
+            .filter(|part| !MODIFIER_KEYS.contains(part))
+            .collect();
+        if !matches!(keys.as_slice(), [key] if is_valid_key(key)) {
+            return Err(invalid());
+        }
+    }
+    Ok(chords.join(" "))
+}
+
+fn is_valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => c.is_ascii_graphic(),
+        _ => {
+            NAMED_KEYS.contains(&key)
+                || function_key(key).as_deref() == Some(key)
+                || numpad_key(key).as_deref() == Some(key)
+        }
+    }
+}
+
+// ============================================================================
+// Export / Import Shortcuts
+// ============================================================================
+// Just the keybindings, so they can be shared without prompts or other settings.
+#[tauri::command]
+pub fn export_shortcuts_command(
+    app: AppHandle,
+    state: State<Mutex<Option<WhisperSettings>>>,
+) -> Result<String, String> {
+    let shortcuts = current_shortcuts(&app, &state)?;
+
+    serde_json::to_string_pretty(&shortcuts)
+        .map_err(|e| format!("Failed to serialize shortcuts: {}", e))
+}
+
+// Only the custom bindings of known commands are taken from the import;
+// titles, descriptions and defaults always come from the app.
+#[tauri::command]
+pub fn import_shortcuts_command(
+    json: String,
+    state: State<Mutex<Option<WhisperSettings>>>,
+    app: AppHandle,
+) -> Result<bool, String> {
+    check_size("Shortcuts JSON", json.len(), MAX_SETTINGS_BYTES)?;
+    let imported: HashMap<String, ShortcutEntry> =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid shortcuts JSON: {}", e))?;
+
+    let mut shortcuts = current_shortcuts(&app, &state)?;
+    let merged = merge_shortcuts(&mut shortcuts, &imported)?;
+    let settings = update_settings(&app, &state, |settings| settings.shortcuts = shortcuts)?;
+
+    register_shortcuts(&app, &settings)?;
+    info!("📥 Imported {} shortcuts", merged);
+    Ok(true)
+}
+
+fn current_shortcuts(
+    app: &AppHandle,
+    state: &Mutex<Option<WhisperSettings>>,
+) -> Result<HashMap<String, ShortcutEntry>, String> {
+    match state.lock().map_err(|e| e.to_string())?.as_ref() {
+        Some(settings) => Ok(settings.shortcuts.clone()),
+        None => Ok(load_settings(app)?.shortcuts),
+    }
+}
+
+// Validates everything before changing anything, so a bad import is all-or-nothing
+pub fn merge_shortcuts(
+    current: &mut HashMap<String, ShortcutEntry>,
+    imported: &HashMap<String, ShortcutEntry>,
+) -> Result<usize, String> {
+    let mut unknown: Vec<&str> = imported
+        .keys()
+        .filter(|key| !current.contains_key(*key))
+        .map(String::as_str)
+        .collect();
+    if !unknown.is_empty() {
+        unknown.sort_unstable();
+        return Err(format!("Unknown shortcut commands: {}", unknown.join(", ")));
+    }
+
+    for (key, entry) in imported {
+        let Some(custom) = &entry.custom_shortcut else {
+            continue;
+        };
+        for (platform, shortcut) in [("mac", &custom.mac), ("windows", &custom.windows)] {
+            if let Some(shortcut) = shortcut.as_deref().filter(|s| !s.is_empty()) {
+                validate_accelerator(shortcut, platform == "mac")
+                    .map_err(|e| format!("Invalid {} shortcut for '{}': {}", platform, key, e))?;
+            }
+        }
+    }
+
+    for (key, entry) in imported {
+        if let Some(current_entry) = current.get_mut(key) {
+            current_entry.custom_shortcut = entry.custom_shortcut.clone();
+        }
+    }
+
+    Ok(imported.len())
+}
+
+// ============================================================================
+// Shortcut Conflicts
+// ============================================================================
+// Pairs of commands bound to the same accelerator on this platform, plus a
+// warning for each command bound to a system shortcut. Checks the given
+// (unsaved) shortcuts if any, otherwise the current ones.
+#[tauri::command]
+pub fn check_shortcut_conflicts_command(
+    app: AppHandle,
+    state: State<Mutex<Option<WhisperSettings>>>,
+    shortcuts: Option<HashMap<String, ShortcutEntry>>,
+) -> Result<ShortcutCheck, String> {
+    let shortcuts = match shortcuts {
+        Some(shortcuts) => shortcuts,
+        None => current_shortcuts(&app, &state)?,
+    };
+    let is_mac = cfg!(target_os = "macos");
+
+    let mut keys: Vec<&String> = shortcuts.keys().collect();
+    keys.sort_unstable();
+    let warnings = keys
+        .into_iter()
+        .filter_map(|key| {
+            let accelerator = parse_shortcut(effective_shortcut(&shortcuts[key], is_mac), is_mac)?;
+            let warning = system_shortcut_warning(&accelerator, is_mac)?;
+            warn!("⚠️  Shortcut '{}': {}", key, warning);
+            Some(format!("'{}': {}", key, warning))
+        })
+        .collect();
+
+    Ok(ShortcutCheck {
+        conflicts: shortcut_conflicts(&shortcuts, is_mac),
+        warnings,
+    })
+}
+
+pub fn check_shortcut_conflicts(
+    shortcuts: &HashMap<String, ShortcutEntry>,
+    is_mac: bool,
+) -> Result<(), String> {
+    let conflicts = shortcut_conflicts(shortcuts, is_mac);
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+
+    let pairs: Vec<String> = conflicts
+        .iter()
+        .map(|(a, b)| format!("'{}' and '{}'", a, b))
+        .collect();
+    Err(format!("Shortcuts use the same keys: {}", pairs.join(", ")))
+}
+
+// Each command that reuses an accelerator is paired with the first one (by
+// key) that has it
+fn shortcut_conflicts(
+    shortcuts: &HashMap<String, ShortcutEntry>,
+    is_mac: bool,
+) -> Vec<(String, String)> {
+    let mut keys: Vec<&String> = shortcuts.keys().collect();
+    keys.sort_unstable();
+
+    let mut owners: HashMap<String, &String> = HashMap::new();
+    let mut conflicts = Vec::new();
+    for key in keys {
+        let Some(accelerator) =
+            parse_shortcut_sequence(effective_shortcut(&shortcuts[key], is_mac), is_mac)
+                .map(|chords| chords.join(" "))
+        else {
+            continue;
+        };
+        match owners.get(&accelerator) {
+            Some(owner) => conflicts.push(((*owner).clone(), key.clone())),
+            None => {
+                owners.insert(accelerator, key);
+            }
+        }
+    }
+    conflicts
+}
+
+// ============================================================================
+// System Shortcuts
+// ============================================================================
+// RESERVED ones belong to the OS and usually can't be registered at all;
+// SYSTEM_HOTKEYS can be, but then stop doing their usual thing everywhere.
+// Both are still attempted (the OS has the final say) with a warning.
+const RESERVED_MAC: &[&str] = &[
+    "Cmd+Q",
+    "Cmd+Tab",
+    "Cmd+Shift+Tab",
+    "Cmd+Alt+Escape",
+    "Ctrl+Cmd+Q",
+];
+const SYSTEM_HOTKEYS_MAC: &[&str] = &[
+    "Cmd+Space",
+    "Ctrl+Space",
+    "Cmd+Shift+3",
+    "Cmd+Shift+4",
+    "Cmd+Shift+5",
+    "Ctrl+Up",
+    "Ctrl+Down",
+    "Ctrl+Left",
+    "Ctrl+Right",
+];
+const RESERVED_WINDOWS: &[&str] = &[
+    "Ctrl+Alt+Delete",
+    "Alt+Tab",
+    "Ctrl+Shift+Escape",
+    "Ctrl+Escape",
+];
+const SYSTEM_HOTKEYS_WINDOWS: &[&str] = &["Alt+F4", "Alt+Space", "Alt+Escape", "Ctrl+Alt+Tab"];
+
+// Spellings of the Windows key; every combination with it belongs to Windows
+const WINDOWS_KEY_NAMES: [&str; 3] = ["Win", "Super", "Meta"];
+
+pub fn system_shortcut_warning(accelerator: &str, is_mac: bool) -> Option<String> {
+    let (reserved, system, os) = if is_mac {
+        (RESERVED_MAC, SYSTEM_HOTKEYS_MAC, "macOS")
     } else {
-        error!("❌ Shortcut command '{}' not found", command_key);
-        Err(format!("Shortcut command '{}' not found", command_key))
+        (RESERVED_WINDOWS, SYSTEM_HOTKEYS_WINDOWS, "the system")
+    };
+    let is = |list: &[&str]| list.iter().any(|other| same_keys(accelerator, other));
+
+    let windows_key = !is_mac
+        && accelerator
+            .split('+')
+            .any(|part| WINDOWS_KEY_NAMES.contains(&part));
+    if windows_key || is(reserved) {
+        Some(format!(
+            "{} is reserved by {} and usually can't be registered",
+            accelerator, os
+        ))
+    } else if is(system) {
+        Some(format!(
+            "{} is a {} shortcut, which stops working while WhisprGPT uses it",
+            accelerator, os
+        ))
+    } else {
+        None
+    }
+}
+
+// The same keys regardless of modifier order
+fn same_keys(a: &str, b: &str) -> bool {
+    let keys = |accelerator: &str| {
+        let mut parts: Vec<String> = accelerator.split('+').map(str::to_lowercase).collect();
+        parts.sort_unstable();
+        parts
+    };
+    keys(a) == keys(b)
+}
+
+// ============================================================================
+// Swap Shortcuts
+// ============================================================================
+// Exchanges the effective accelerators of two commands in one step, so there's
+// never a moment where both use the same one.
+#[tauri::command]
+pub fn swap_shortcuts_command(
+    key_a: String,
+    key_b: String,
+    platform: String,
+    state: State<Mutex<Option<WhisperSettings>>>,
+    app_handle: AppHandle,
+) -> Result<bool, String> {
+    info!(
+        "🔀 Swapping shortcuts '{}' and '{}' on {}",
+        key_a, key_b, platform
+    );
+
+    let settings = try_update_settings(&app_handle, &state, |settings| {
+        swap_shortcuts(&mut settings.shortcuts, &key_a, &key_b, platform == "mac")
+    })?;
+
+    register_shortcuts(&app_handle, &settings)?;
+    Ok(true)
+}
+
+pub fn swap_shortcuts(
+    shortcuts: &mut HashMap<String, ShortcutEntry>,
+    key_a: &str,
+    key_b: &str,
+    is_mac: bool,
+) -> Result<(), String> {
+    let shortcut_a = shortcuts
+        .get(key_a)
+        .map(|entry| effective_shortcut(entry, is_mac).to_string())
+        .ok_or_else(|| format!("Shortcut command '{}' not found", key_a))?;
+    let shortcut_b = shortcuts
+        .get(key_b)
+        .map(|entry| effective_shortcut(entry, is_mac).to_string())
+        .ok_or_else(|| format!("Shortcut command '{}' not found", key_b))?;
+
+    for (key, shortcut) in [(key_a, shortcut_b), (key_b, shortcut_a)] {
+        let entry = shortcuts.get_mut(key).unwrap();
+        let custom = entry.custom_shortcut.get_or_insert(CustomShortcut {
+            mac: None,
+            windows: None,
+        });
+        if is_mac {
+            custom.mac = Some(shortcut);
+        } else {
+            custom.windows = Some(shortcut);
+        }
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Global Shortcut Support Probe
+// ============================================================================
+// Some sessions (notably Wayland) refuse global shortcuts without any error
+// the app would notice. Probe once with a throwaway accelerator and cache it.
+const PROBE_ACCELERATOR: &str = "Ctrl+Alt+Shift+F19";
+
+#[tauri::command]
+pub fn global_shortcuts_supported_command(app: AppHandle) -> Result<bool, String> {
+    global_shortcuts_supported(&app)
+}
+
+// Cached; also what get_feature_support_command reports
+pub fn global_shortcuts_supported(app: &AppHandle) -> Result<bool, String> {
+    let support = app.state::<ShortcutSupport>();
+    let mut cached = support.supported.lock().map_err(|e| e.to_string())?;
+    if let Some(supported) = *cached {
+        return Ok(supported);
+    }
+
+    let supported = probe_global_shortcuts(&mut app.global_shortcut_manager(), is_wayland());
+    info!("⌨️  Global shortcuts supported: {}", supported);
+
+    *cached = Some(supported);
+    Ok(supported)
+}
+
+// The part of GlobalShortcutManager the probe needs, so it can be tested
+pub trait ShortcutProbe {
+    fn try_register(&mut self, accelerator: &str) -> Result<(), String>;
+    fn unregister(&mut self, accelerator: &str) -> Result<(), String>;
+}
+
+impl<M: GlobalShortcutManager> ShortcutProbe for M {
+    fn try_register(&mut self, accelerator: &str) -> Result<(), String> {
+        self.register(accelerator, || {}).map_err(|e| e.to_string())
+    }
+
+    fn unregister(&mut self, accelerator: &str) -> Result<(), String> {
+        GlobalShortcutManager::unregister(self, accelerator).map_err(|e| e.to_string())
+    }
+}
+
+// Uncached, for diagnostics (run_self_test_command)
+pub fn probe_global_shortcuts_now(app: &AppHandle) -> bool {
+    probe_global_shortcuts(&mut app.global_shortcut_manager(), is_wayland())
+}
+
+fn probe_global_shortcuts(manager: &mut impl ShortcutProbe, is_wayland: bool) -> bool {
+    if is_wayland {
+        return false;
+    }
+
+    match manager.try_register(PROBE_ACCELERATOR) {
+        Ok(()) => {
+            if let Err(e) = manager.unregister(PROBE_ACCELERATOR) {
+                error!("Failed to unregister probe shortcut: {}", e);
+            }
+            true
+        }
+        Err(e) => {
+            debug!("Probe shortcut registration failed: {}", e);
+            false
+        }
+    }
+}
+
+fn is_wayland() -> bool {
+    cfg!(target_os = "linux")
+        && (std::env::var("XDG_SESSION_TYPE").is_ok_and(|session| session == "wayland")
+            || std::env::var_os("WAYLAND_DISPLAY").is_some())
+}
+
+// Managed state: cached probe result
+#[derive(Default)]
+pub struct ShortcutSupport {
+    supported: Mutex<Option<bool>>,
+}
+
+// ============================================================================
+// Text Input Awareness
+// ============================================================================
+// Global shortcuts fire even while the user is typing, so e.g. Ctrl+Left
+// would move the window instead of just jumping a word. With the
+// respect_text_input setting, shortcuts that double as text editing keys
+// skip their action while a text field in the main window has focus. The page
+// (dashboard or provider) reports that through TEXT_INPUT_REPORTER_SCRIPT.
+
+// Keys that edit or move through text when combined with a modifier
+const TEXT_EDITING_KEYS: [&str; 17] = [
+    "Up",
+    "Down",
+    "Left",
+    "Right",
+    "Home",
+    "End",
+    "Backspace",
+    "Delete",
+    "A",
+    "B",
+    "C",
+    "I",
+    "U",
+    "V",
+    "X",
+    "Y",
+    "Z",
+];
+
+// Focus in a text field of a focused window counts; switching to another app
+// doesn't leave the overlay's prompt box "focused"
+const TEXT_INPUT_REPORTER_SCRIPT: &str = r#"(function () {
+  if (window.__whisprTextInputReporter) return;
+  window.__whisprTextInputReporter = true;
+
+  var TEXT_TYPES = ['', 'text', 'search', 'email', 'url', 'tel', 'password', 'number'];
+  var last = null;
+  function isTextField(el) {
+    if (!el) return false;
+    if (el.isContentEditable || el.tagName === 'TEXTAREA') return true;
+    return el.tagName === 'INPUT' &&
+      TEXT_TYPES.indexOf((el.getAttribute('type') || '').toLowerCase()) !== -1;
+  }
+  function report() {
+    var focused = document.hasFocus() && isTextField(document.activeElement);
+    if (!window.__TAURI_INVOKE__ || focused === last) return;
+    last = focused;
+    window.__TAURI_INVOKE__('report_text_input_focus_command', { focused: focused })
+      .catch(function () {});
+  }
+  // activeElement only moves on after focusout
+  function reportLater() {
+    setTimeout(report, 0);
+  }
+
+  document.addEventListener('focusin', report, true);
+  document.addEventListener('focusout', reportLater, true);
+  window.addEventListener('focus', report);
+  window.addEventListener('blur', reportLater);
+  report();
+})()"#;
+
+// Called from the page-load hook in main.rs for every new document
+pub fn install_text_input_reporter(window: &Window) {
+    // Whatever had focus went away with the previous document
+    window
+        .state::<TextInputFocus>()
+        .focused
+        .store(false, Ordering::SeqCst);
+
+    if let Err(e) = window.eval(TEXT_INPUT_REPORTER_SCRIPT) {
+        error!("❌ Failed to install text input reporter: {}", e);
+    }
+}
+
+// Called by TEXT_INPUT_REPORTER_SCRIPT
+#[tauri::command]
+pub fn report_text_input_focus_command(focus: State<TextInputFocus>, focused: bool) {
+    focus.focused.store(focused, Ordering::SeqCst);
+}
+
+// Checked at the start of every global shortcut callback
+pub fn suppressed_for_text_input(app: &AppHandle, accelerator: &str) -> bool {
+    let focused = match app.try_state::<TextInputFocus>() {
+        Some(focus) => focus.focused.load(Ordering::SeqCst),
+        None => false,
+    };
+    let respect = match app
+        .state::<Mutex<Option<WhisperSettings>>>()
+        .lock()
+        .unwrap()
+        .as_ref()
+    {
+        Some(settings) => settings.respect_text_input,
+        None => WhisperSettings::default().respect_text_input,
+    };
+
+    let suppressed = suppresses_text_input(respect, focused, accelerator);
+    if suppressed {
+        debug!("⌨️  Skipped {} while typing", accelerator);
+    }
+    suppressed
+}
+
+fn suppresses_text_input(respect: bool, focused: bool, accelerator: &str) -> bool {
+    respect && focused && is_text_editing_accelerator(accelerator)
+}
+
+fn is_text_editing_accelerator(accelerator: &str) -> bool {
+    let Some((modifiers, key)) = accelerator.rsplit_once('+') else {
+        return false;
+    };
+    modifiers
+        .split('+')
+        .any(|modifier| matches!(modifier, "Ctrl" | "Cmd" | "Alt"))
+        && TEXT_EDITING_KEYS.contains(&key)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ShortcutAction<'a> {
+    Move(i32, i32),
+    NextMonitor,
+    ToggleVisibility,
+    PanicHide,
+    OpacityUp,
+    OpacityDown,
+    ToggleAlwaysOnTop,
+    NavigateBack,
+    Home,
+    CycleWindowSlots,
+    RunTemplate(&'a str),
+    Emit,
+}
+
+// Managed state: whether a text field in the frontend has focus
+#[derive(Default)]
+pub struct TextInputFocus {
+    focused: AtomicBool,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct RegisteredShortcut {
+    pub command_key: String,
+    pub accelerator: String,
+    pub registered: bool,
+    // Set for reserved and system shortcuts (system_shortcut_warning)
+    pub warning: Option<String>,
+    // Why it wasn't registered
+    pub error: Option<String>,
+}
+
+// What register_shortcuts got done: command keys whose accelerators all
+// registered, and (command key, error) for every one that didn't
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct RegistrationResult {
+    pub registered: Vec<String>,
+    pub failed: Vec<(String, String)>,
+}
+
+impl RegistrationResult {
+    fn from_results(results: &[RegisteredShortcut]) -> Self {
+        let mut failed: Vec<(String, String)> = results
+            .iter()
+            .filter_map(|result| {
+                let error = result.error.as_ref()?;
+                let error = format!("{}: {}", result.accelerator, error);
+                Some((result.command_key.clone(), error))
+            })
+            .collect();
+        failed.sort();
+
+        let mut registered: Vec<String> = results
+            .iter()
+            .map(|result| result.command_key.clone())
+            .filter(|key| !failed.iter().any(|(failed_key, _)| failed_key == key))
+            .collect();
+        registered.sort();
+        registered.dedup();
+
+        RegistrationResult { registered, failed }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ShortcutCheck {
+    pub conflicts: Vec<(String, String)>,
+    pub warnings: Vec<String>,
+}
+
+// Managed state: the registered chord sequences and the one being typed
+#[derive(Default)]
+pub struct ChordSequences {
+    state: Mutex<SequenceState>,
+}
+
+#[derive(Default)]
+struct SequenceState {
+    // (chords, command key)
+    sequences: Vec<(Vec<String>, String)>,
+    // Accelerators registered by register_shortcuts
+    registered: Vec<String>,
+    typed: Vec<String>,
+    // Follow-up chords registered for the sequence being typed
+    temporary: Vec<String>,
+    // Bumped on every change, so a stale timeout does nothing
+    generation: u64,
+}
+
+impl ChordSequences {
+    fn reset(&self, sequences: Vec<(Vec<String>, String)>, registered: Vec<String>) {
+        let mut state = self.state.lock().unwrap();
+        state.sequences = sequences;
+        state.registered = registered;
+        state.typed.clear();
+        state.temporary.clear();
+        state.generation += 1;
+    }
+}
+
+// Managed state: the shortcut recording in progress, if any. A newer recording
+// replaces an older one, whose keypress is then ignored.
+#[derive(Default)]
+pub struct ShortcutRecording {
+    next_id: AtomicU64,
+    active: Mutex<Option<u64>>,
+}
+
+impl ShortcutRecording {
+    fn start(&self) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        *self.active.lock().unwrap() = Some(id);
+        id
+    }
+
+    // True only for the first finish of the current recording
+    fn finish(&self, id: u64) -> bool {
+        let mut active = self.active.lock().unwrap();
+        if *active == Some(id) {
+            *active = None;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+// Managed state: the outcome of the last register_shortcuts
+#[derive(Default)]
+pub struct RegisteredShortcuts {
+    last: Mutex<Vec<RegisteredShortcut>>,
+}
+
+impl RegisteredShortcuts {
+    fn set(&self, mut shortcuts: Vec<RegisteredShortcut>) {
+        shortcuts.sort_by(|a, b| {
+            (&a.command_key, &a.accelerator).cmp(&(&b.command_key, &b.accelerator))
+        });
+        *self.last.lock().unwrap() = shortcuts;
+    }
+
+    fn list(&self) -> Vec<RegisteredShortcut> {
+        self.last.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn imported_entry(current: &ShortcutEntry, windows: &str) -> ShortcutEntry {
+        ShortcutEntry {
+            title: "Renamed by a teammate".to_string(),
+            custom_shortcut: Some(CustomShortcut {
+                mac: None,
+                windows: Some(windows.to_string()),
+            }),
+            ..current.clone()
+        }
+    }
+
+    #[test]
+    fn modifiers_are_put_in_canonical_order() {
+        assert_eq!(
+            parse_shortcut("Shift+Ctrl+T", false),
+            parse_shortcut("Ctrl+Shift+T", false)
+        );
+        assert_eq!(
+            parse_shortcut("Shift+Ctrl+T", false).as_deref(),
+            Some("Ctrl+Shift+T")
+        );
+        assert_eq!(
+            parse_shortcut("Ctrl+Ctrl+A", false).as_deref(),
+            Some("Ctrl+A")
+        );
+        assert_eq!(
+            parse_shortcut("⌘ + Shift + ⌥ + ↑", true).as_deref(),
+            Some("Alt+Shift+Cmd+Up")
+        );
+        // ⌘ is Ctrl off the mac, so it merges with an explicit Ctrl
+        assert_eq!(parse_shortcut("Ctrl+⌘+A", false).as_deref(), Some("Ctrl+A"));
+        assert_eq!(with_shift("Cmd+Up").as_deref(), Some("Shift+Cmd+Up"));
+    }
+
+    #[test]
+    fn function_and_numpad_keys_are_named() {
+        for (verbose, expected) in [
+            ("F12", "F12"),
+            ("ctrl+f1", "Ctrl+F1"),
+            ("Shift + F24", "Shift+F24"),
+            ("PageDown", "PageDown"),
+            ("Ctrl+pgup", "Ctrl+PageUp"),
+            ("home", "Home"),
+            ("Alt+del", "Alt+Delete"),
+            ("Ctrl+Backspace", "Ctrl+Backspace"),
+            ("Ctrl+numpad7", "Ctrl+Num7"),
+            ("Alt+NumAdd", "Alt+NumAdd"),
+        ] {
+            assert_eq!(parse_shortcut(verbose, false).as_deref(), Some(expected));
+            assert!(validate_accelerator(verbose, false).is_ok(), "{}", verbose);
+        }
+        assert!(validate_accelerator("Ctrl+Numpad", false).is_err());
+    }
+
+    #[test]
+    fn a_recording_finishes_once() {
+        let recording = ShortcutRecording::default();
+        let first = recording.start();
+        let second = recording.start();
+
+        // The first recording was replaced
+        assert!(!recording.finish(first));
+        assert!(recording.finish(second));
+        // A keypress after the timeout (or vice versa) is ignored
+        assert!(!recording.finish(second));
+    }
+
+    #[test]
+    fn registration_results_split_by_command() {
+        let shortcut = |key: &str, accelerator: &str, error: Option<&str>| RegisteredShortcut {
+            command_key: key.to_string(),
+            accelerator: accelerator.to_string(),
+            registered: error.is_none(),
+            warning: None,
+            error: error.map(str::to_string),
+        };
+        let results = [
+            shortcut("move-left", "Ctrl+Left", None),
+            shortcut("move-left", "Ctrl+Shift+Left", None),
+            shortcut("generate", "Ctrl+Enter", None),
+            shortcut("screenshot", "Ctrl+K Ctrl+S", Some("Ctrl+K is taken")),
+            shortcut("move-up", "Ctrl+Up", None),
+            shortcut("move-up", "Ctrl+Shift+Up", Some("in use")),
+        ];
+
+        assert_eq!(
+            RegistrationResult::from_results(&results),
+            RegistrationResult {
+                registered: vec!["generate".to_string(), "move-left".to_string()],
+                failed: vec![
+                    ("move-up".to_string(), "Ctrl+Shift+Up: in use".to_string()),
+                    (
+                        "screenshot".to_string(),
+                        "Ctrl+K Ctrl+S: Ctrl+K is taken".to_string()
+                    ),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn accelerators_are_validated() {
+        assert_eq!(
+            validate_accelerator("Ctrl + Shift + T", false),
+            Ok("Ctrl+Shift+T".to_string())
+        );
+        assert_eq!(
+            validate_accelerator("⌘ + ↵", true),
+            Ok("Cmd+Enter".to_string())
+        );
+        assert!(validate_accelerator("Alt+F12", false).is_ok());
+
+        for shortcut in ["", "Ctrl+", "Foo+Bar", "Ctrl+Shift", "Ctrl+A+B", "Ctrl+F25"] {
+            assert_eq!(
+                validate_accelerator(shortcut, false),
+                Err(format!("'{}' is not a valid accelerator", shortcut))
+            );
+        }
+    }
+
+    #[test]
+    fn conflicting_shortcuts_are_paired() {
+        let mut shortcuts = WhisperSettings::default().shortcuts;
+        assert!(shortcut_conflicts(&shortcuts, false).is_empty());
+        assert!(check_shortcut_conflicts(&shortcuts, true).is_ok());
+
+        let generate = shortcuts["generate"].default_shortcut.windows.clone();
+        let retry = shortcuts.get_mut("retry-prompt").unwrap();
+        retry.custom_shortcut = Some(CustomShortcut {
+            mac: None,
+            windows: Some(generate.to_lowercase()),
+        });
+
+        assert_eq!(
+            shortcut_conflicts(&shortcuts, false),
+            vec![("generate".to_string(), "retry-prompt".to_string())]
+        );
+        assert_eq!(
+            check_shortcut_conflicts(&shortcuts, false),
+            Err("Shortcuts use the same keys: 'generate' and 'retry-prompt'".to_string())
+        );
+        // The mac bindings are untouched
+        assert!(shortcut_conflicts(&shortcuts, true).is_empty());
+    }
+
+    #[test]
+    fn shortcut_keys_map_to_actions() {
+        assert_eq!(shortcut_action("move-left"), ShortcutAction::Move(-1, 0));
+        assert_eq!(shortcut_action("move-down"), ShortcutAction::Move(0, 1));
+        assert_eq!(with_shift("Ctrl+Left").as_deref(), Some("Ctrl+Shift+Left"));
+        assert_eq!(with_shift("Ctrl+Shift+Up"), None);
+        assert_eq!(
+            shortcut_action("hide-show"),
+            ShortcutAction::ToggleVisibility
+        );
+        assert_eq!(shortcut_action("next-monitor"), ShortcutAction::NextMonitor);
+        assert_eq!(shortcut_action("panic"), ShortcutAction::PanicHide);
+        assert_eq!(shortcut_action("opacity-up"), ShortcutAction::OpacityUp);
+        assert_eq!(shortcut_action("opacity-down"), ShortcutAction::OpacityDown);
+        assert_eq!(
+            shortcut_action("always-on-top"),
+            ShortcutAction::ToggleAlwaysOnTop
+        );
+        assert_eq!(shortcut_action("back"), ShortcutAction::NavigateBack);
+        assert_eq!(shortcut_action("home"), ShortcutAction::Home);
+        assert_eq!(
+            shortcut_action("cycle-window-slots"),
+            ShortcutAction::CycleWindowSlots
+        );
+        assert_eq!(
+            shortcut_action("template:summarize"),
+            ShortcutAction::RunTemplate("summarize")
+        );
+        for key in ["screenshot", "generate", "quit", "added-in-a-later-version"] {
+            assert_eq!(shortcut_action(key), ShortcutAction::Emit);
+        }
+    }
+
+    #[test]
+    fn text_editing_shortcuts_pause_while_typing() {
+        for accelerator in ["Ctrl+Left", "Cmd+Shift+Right", "Ctrl+A", "Alt+Backspace"] {
+            assert!(suppresses_text_input(true, true, accelerator));
+            // Toggled off by the setting, or nothing is focused
+            assert!(!suppresses_text_input(false, true, accelerator));
+            assert!(!suppresses_text_input(true, false, accelerator));
+        }
+
+        for accelerator in ["Ctrl+H", "Ctrl+Shift+S", "Left", "F5"] {
+            assert!(!suppresses_text_input(true, true, accelerator));
+        }
+    }
+
+    #[test]
+    fn import_merges_only_custom_bindings() {
+        let mut current = WhisperSettings::default().shortcuts;
+        let imported = HashMap::from([(
+            "screenshot".to_string(),
+            imported_entry(&current["screenshot"], "Ctrl + Shift + S"),
+        )]);
+
+        assert_eq!(merge_shortcuts(&mut current, &imported), Ok(1));
+
+        let screenshot = &current["screenshot"];
+        assert_eq!(screenshot.title, "Screenshot");
+        assert_eq!(
+            screenshot
+                .custom_shortcut
+                .as_ref()
+                .unwrap()
+                .windows
+                .as_deref(),
+            Some("Ctrl + Shift + S")
+        );
+        assert!(current["generate"].custom_shortcut.is_none());
+    }
+
+    #[test]
+    fn import_rejects_unknown_commands_and_bad_bindings() {
+        let mut current = WhisperSettings::default().shortcuts;
+        let screenshot = current["screenshot"].clone();
+
+        let unknown = HashMap::from([
+            (
+                "screenshot".to_string(),
+                imported_entry(&screenshot, "Ctrl + 1"),
+            ),
+            (
+                "self-destruct".to_string(),
+                imported_entry(&screenshot, "Ctrl + 2"),
+            ),
+        ]);
+        assert_eq!(
+            merge_shortcuts(&mut current, &unknown),
+            Err("Unknown shortcut commands: self-destruct".to_string())
+        );
+
+        let invalid =
+            HashMap::from([("screenshot".to_string(), imported_entry(&screenshot, " + "))]);
+        assert!(merge_shortcuts(&mut current, &invalid).is_err());
+
+        // Parses, but isn't a real key
+        let invalid = HashMap::from([(
+            "screenshot".to_string(),
+            imported_entry(&screenshot, "Foo+Bar"),
+        )]);
+        assert!(merge_shortcuts(&mut current, &invalid).is_err());
+
+        // Nothing was applied by the rejected imports
+        assert!(current["screenshot"].custom_shortcut.is_none());
+    }
+
+    struct MockManager {
+        accepts: bool,
+        registered: Vec<String>,
+    }
+
+    impl ShortcutProbe for MockManager {
+        fn try_register(&mut self, accelerator: &str) -> Result<(), String> {
+            if !self.accepts {
+                return Err("registration refused".to_string());
+            }
+            self.registered.push(accelerator.to_string());
+            Ok(())
+        }
+
+        fn unregister(&mut self, accelerator: &str) -> Result<(), String> {
+            self.registered
+                .retain(|registered| registered != accelerator);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn probe_reports_support_and_cleans_up() {
+        let mut manager = MockManager {
+            accepts: true,
+            registered: Vec::new(),
+        };
+        assert!(probe_global_shortcuts(&mut manager, false));
+        assert!(manager.registered.is_empty());
+    }
+
+    #[test]
+    fn probe_fails_when_registration_is_refused_or_on_wayland() {
+        let mut refusing = MockManager {
+            accepts: false,
+            registered: Vec::new(),
+        };
+        assert!(!probe_global_shortcuts(&mut refusing, false));
+
+        let mut accepting = MockManager {
+            accepts: true,
+            registered: Vec::new(),
+        };
+        assert!(!probe_global_shortcuts(&mut accepting, true));
+        assert!(accepting.registered.is_empty());
+    }
+
+    #[test]
+    fn swap_exchanges_effective_bindings() {
+        let mut shortcuts = WhisperSettings::default().shortcuts;
+        let screenshot = effective_shortcut(&shortcuts["screenshot"], false).to_string();
+        let generate = effective_shortcut(&shortcuts["generate"], false).to_string();
+
+        swap_shortcuts(&mut shortcuts, "screenshot", "generate", false).unwrap();
+
+        assert_eq!(
+            effective_shortcut(&shortcuts["screenshot"], false),
+            generate
+        );
+        assert_eq!(
+            effective_shortcut(&shortcuts["generate"], false),
+            screenshot
+        );
+        // The other platform is untouched
+        assert!(shortcuts["screenshot"]
+            .custom_shortcut
+            .as_ref()
+            .unwrap()
+            .mac
+            .is_none());
+    }
+
+    #[test]
+    fn swap_with_a_missing_key_changes_nothing() {
+        let mut shortcuts = WhisperSettings::default().shortcuts;
+
+        assert_eq!(
+            swap_shortcuts(&mut shortcuts, "screenshot", "teleport", false),
+            Err("Shortcut command 'teleport' not found".to_string())
+        );
+        assert!(shortcuts["screenshot"].custom_shortcut.is_none());
+    }
+
+    #[test]
+    fn registration_results_replace_the_previous_ones() {
+        let result = |command_key: &str, accelerator: &str, registered| RegisteredShortcut {
+            command_key: command_key.to_string(),
+            accelerator: accelerator.to_string(),
+            registered,
+            warning: None,
+            error: (!registered).then(|| "in use".to_string()),
+        };
+        let state = RegisteredShortcuts::default();
+
+        state.set(vec![result("screenshot", "Ctrl+S", true)]);
+        state.set(vec![
+            result("move-up", "Ctrl+Up", true),
+            result("home", "Ctrl+B", false),
+            result("move-up", "Ctrl+Shift+Up", false),
+        ]);
+        assert_eq!(
+            state.list(),
+            vec![
+                result("home", "Ctrl+B", false),
+                result("move-up", "Ctrl+Shift+Up", false),
+                result("move-up", "Ctrl+Up", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn system_shortcuts_get_a_warning() {
+        let reserved = |accelerator, is_mac| {
+            system_shortcut_warning(accelerator, is_mac).is_some_and(|w| w.contains("reserved"))
+        };
+
+        // macOS
+        assert!(reserved("Cmd+Q", true));
+        assert!(reserved("Cmd+Shift+Tab", true));
+        assert!(system_shortcut_warning("Cmd+Space", true).is_some());
+        assert!(system_shortcut_warning("Cmd+Up", true).is_none());
+
+        // Windows, including any modifier order and the Windows key
+        assert!(reserved("Alt+Ctrl+Delete", false));
+        assert!(reserved("Win+D", false));
+        assert!(system_shortcut_warning("Alt+F4", false).is_some());
+        assert!(!reserved("Alt+F4", false));
+        assert!(system_shortcut_warning("Ctrl+Up", false).is_none());
+
+        // Only the platform's own list counts
+        assert!(system_shortcut_warning("Cmd+Q", false).is_none());
+    }
+
+    #[test]
+    fn shortcut_sequences_are_parsed_and_matched() {
+        assert_eq!(
+            parse_shortcut_sequence("Ctrl + K Ctrl + S", false),
+            Some(vec!["Ctrl+K".to_string(), "Ctrl+S".to_string()])
+        );
+        assert_eq!(
+            parse_shortcut_sequence("⌘ + K ⌘ + ↑", true),
+            Some(vec!["Cmd+K".to_string(), "Cmd+Up".to_string()])
+        );
+        // One chord is the same as parse_shortcut
+        assert_eq!(
+            parse_shortcut_sequence("Ctrl + Shift + T", false),
+            Some(vec!["Ctrl+Shift+T".to_string()])
+        );
+        assert_eq!(parse_shortcut_sequence("   ", false), None);
+        assert_eq!(
+            validate_accelerator("Ctrl+K  Ctrl+S", false),
+            Ok("Ctrl+K Ctrl+S".to_string())
+        );
+        assert!(validate_accelerator("Ctrl+K Ctrl", false).is_err());
+
+        let chords = |list: &str| list.split(' ').map(str::to_string).collect::<Vec<_>>();
+        let sequences = vec![
+            (chords("Ctrl+K Ctrl+S"), "screenshot".to_string()),
+            (chords("Ctrl+K Ctrl+T"), "retry-prompt".to_string()),
+        ];
+        assert_eq!(
+            sequence_step(&sequences, &chords("Ctrl+K")),
+            SequenceStep::Partial(chords("Ctrl+S Ctrl+T"))
+        );
+        assert_eq!(
+            sequence_step(&sequences, &chords("Ctrl+K Ctrl+T")),
+            SequenceStep::Complete("retry-prompt".to_string())
+        );
+        assert_eq!(
+            sequence_step(&sequences, &chords("Ctrl+K Ctrl+K")),
+            SequenceStep::NoMatch
+        );
+        assert_eq!(
+            sequence_step(&sequences, &chords("Ctrl+S")),
+            SequenceStep::NoMatch
+        );
     }
 }
@@ -0,0 +1,150 @@
+// commands/snapshot.rs
+// QA helpers: capture the app's state (settings + window geometry + route)
+// and restore it between end-to-end test runs.
+// Only available when the app is started with WHISPRGPT_QA=1.
+
+use crate::commands::settings::persist_settings;
+use crate::commands::whispr::{
+    is_dashboard_url, launch_whispr_mode_command, navigate_to_dashboard_command,
+};
+use crate::commands::window::{apply_window_level, WindowLevel};
+use crate::state::settings::load_settings;
+use crate::types::{WhisperSettings, WindowGeometry};
+use log::info;
+use std::sync::Mutex;
+use tauri::{AppHandle, State, Window};
+use url::Url;
+
+const QA_ENV_FLAG: &str = "WHISPRGPT_QA";
+
+// ============================================================================
+// Capture State Command
+// ============================================================================
+#[tauri::command]
+pub fn capture_state_command(
+    app_handle: AppHandle,
+    window: Window,
+    state: State<Mutex<Option<WhisperSettings>>>,
+) -> Result<AppSnapshot, String> {
+    require_qa_mode()?;
+
+    let settings = match state.lock().unwrap().as_ref() {
+        Some(settings) => settings.clone(),
+        None => load_settings(&app_handle)?,
+    };
+
+    let position = window
+        .outer_position()
+        .map_err(|e| format!("Failed to get window position: {}", e))?;
+    let size = window
+        .outer_size()
+        .map_err(|e| format!("Failed to get window size: {}", e))?;
+
+    info!("📸 App state captured");
+    Ok(AppSnapshot {
+        settings,
+        geometry: WindowGeometry {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+        },
+        route: window.url().to_string(),
+    })
+}
+
+// ============================================================================
+// Restore State Command
+// ============================================================================
+#[tauri::command]
+pub fn restore_state_command(
+    app_handle: AppHandle,
+    window: Window,
+    state: State<Mutex<Option<WhisperSettings>>>,
+    snapshot: AppSnapshot,
+) -> Result<(), String> {
+    require_qa_mode()?;
+
+    persist_settings(&app_handle, &snapshot.settings)?;
+    if let Ok(level) = WindowLevel::parse(&snapshot.settings.window_level) {
+        apply_window_level(&window, level)?;
+    }
+    *state.lock().unwrap() = Some(snapshot.settings);
+
+    let geometry = snapshot.geometry;
+    window
+        .set_size(tauri::Size::Physical(tauri::PhysicalSize {
+            width: geometry.width,
+            height: geometry.height,
+        }))
+        .map_err(|e| format!("Failed to set window size: {}", e))?;
+    window
+        .set_position(tauri::Position::Physical(tauri::PhysicalPosition {
+            x: geometry.x,
+            y: geometry.y,
+        }))
+        .map_err(|e| format!("Failed to set window position: {}", e))?;
+
+    if window.url().as_str() != snapshot.route {
+        // Provider pages only; a snapshot taken on the dashboard goes back there
+        if Url::parse(&snapshot.route).is_ok_and(|url| is_dashboard_url(&url)) {
+            navigate_to_dashboard_command(app_handle)?;
+        } else {
+            launch_whispr_mode_command(app_handle, snapshot.route)?;
+        }
+    }
+
+    info!("📸 App state restored");
+    Ok(())
+}
+
+fn require_qa_mode() -> Result<(), String> {
+    if std::env::var(QA_ENV_FLAG)
+        .map(|v| v == "1")
+        .unwrap_or(false)
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "State snapshots are only available when {}=1 is set",
+            QA_ENV_FLAG
+        ))
+    }
+}
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AppSnapshot {
+    pub settings: WhisperSettings,
+    pub geometry: WindowGeometry,
+    pub route: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_round_trips_through_json() {
+        let snapshot = AppSnapshot {
+            settings: WhisperSettings::default(),
+            geometry: WindowGeometry {
+                x: -1200,
+                y: 50,
+                width: 500,
+                height: 400,
+            },
+            route: "https://chatgpt.com/c/abc".to_string(),
+        };
+
+        let json = serde_json::to_value(&snapshot).unwrap();
+        let restored: AppSnapshot = serde_json::from_value(json.clone()).unwrap();
+
+        assert_eq!(restored.geometry, snapshot.geometry);
+        assert_eq!(restored.route, snapshot.route);
+        assert_eq!(serde_json::to_value(&restored).unwrap(), json);
+    }
+}
@@ -0,0 +1,158 @@
+// commands/templates.rs
+// Named prompt templates: a small library on top of the single system_prompt,
+// injected into the provider's prompt box on demand.
+
+use crate::commands::settings::update_settings;
+use crate::commands::whispr::{inject_prompt, provider_for_url};
+use crate::commands::window::require_main_window;
+use crate::limits::check_prompt;
+use crate::state::settings::load_settings;
+use crate::types::WhisperSettings;
+use log::info;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+use url::Url;
+
+// ============================================================================
+// Run Template Command
+// ============================================================================
+// Injects into the main window whichever window asks. Provider pages can't
+// invoke this (see guard_provider_ipc), so while one is showing templates run
+// from a "template:{name}" shortcut instead (see run_shortcut_action).
+#[tauri::command]
+pub fn run_template_command(app_handle: AppHandle, name: String) -> Result<(), String> {
+    run_template(&app_handle, &name)
+}
+
+pub fn run_template(app_handle: &AppHandle, name: &str) -> Result<(), String> {
+    let window = require_main_window(app_handle)?;
+    let templates = current_templates(app_handle, &app_handle.state())?;
+    let template = template_for_run(&templates, name, &window.url())?;
+
+    inject_prompt(&window, template)?;
+    info!("📝 Ran prompt template '{}'", name);
+    Ok(())
+}
+
+// The template to inject, if `name` exists and a provider page is showing
+fn template_for_run<'a>(
+    templates: &'a HashMap<String, String>,
+    name: &str,
+    url: &Url,
+) -> Result<&'a str, String> {
+    let template = templates
+        .get(name)
+        .ok_or_else(|| format!("Unknown prompt template: {}", name))?;
+
+    if provider_for_url(url).is_none() {
+        return Err("No provider page is loaded".to_string());
+    }
+
+    Ok(template)
+}
+
+// ============================================================================
+// Template CRUD Commands
+// ============================================================================
+#[tauri::command]
+pub fn list_templates_command(
+    app_handle: AppHandle,
+    state: State<Mutex<Option<WhisperSettings>>>,
+) -> Result<HashMap<String, String>, String> {
+    current_templates(&app_handle, &state)
+}
+
+// Creates the template or replaces an existing one with the same name
+#[tauri::command]
+pub fn save_template_command(
+    app_handle: AppHandle,
+    state: State<Mutex<Option<WhisperSettings>>>,
+    name: String,
+    template: String,
+) -> Result<(), String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Template name can't be empty".to_string());
+    }
+    check_prompt("Template", &template)?;
+
+    info!("📝 Saving prompt template '{}'", name);
+    update_settings(&app_handle, &state, |settings| {
+        settings.prompt_templates.insert(name, template);
+    })?;
+    Ok(())
+}
+
+// Returns false if there was no template with that name
+#[tauri::command]
+pub fn delete_template_command(
+    app_handle: AppHandle,
+    state: State<Mutex<Option<WhisperSettings>>>,
+    name: String,
+) -> Result<bool, String> {
+    let mut removed = false;
+    update_settings(&app_handle, &state, |settings| {
+        removed = settings.prompt_templates.remove(&name).is_some();
+    })?;
+
+    if removed {
+        info!("🗑️  Deleted prompt template '{}'", name);
+    }
+    Ok(removed)
+}
+
+fn current_templates(
+    app_handle: &AppHandle,
+    state: &Mutex<Option<WhisperSettings>>,
+) -> Result<HashMap<String, String>, String> {
+    match state.lock().unwrap().as_ref() {
+        Some(settings) => Ok(settings.prompt_templates.clone()),
+        None => Ok(load_settings(app_handle)?.prompt_templates),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn templates() -> HashMap<String, String> {
+        HashMap::from([(
+            "summarize".to_string(),
+            "Summarize this in three bullet points".to_string(),
+        )])
+    }
+
+    #[test]
+    fn known_templates_run_on_provider_pages() {
+        let url = Url::parse("https://chatgpt.com/c/123").unwrap();
+        assert_eq!(
+            template_for_run(&templates(), "summarize", &url),
+            Ok("Summarize this in three bullet points")
+        );
+    }
+
+    #[test]
+    fn unknown_templates_are_rejected() {
+        let url = Url::parse("https://chatgpt.com/").unwrap();
+        assert_eq!(
+            template_for_run(&templates(), "translate", &url),
+            Err("Unknown prompt template: translate".to_string())
+        );
+    }
+
+    #[test]
+    fn templates_need_a_provider_page() {
+        for url in [
+            "tauri://localhost/",
+            "http://localhost:1420/",
+            "https://example.com/",
+        ] {
+            let url = Url::parse(url).unwrap();
+            assert_eq!(
+                template_for_run(&templates(), "summarize", &url),
+                Err("No provider page is loaded".to_string())
+            );
+        }
+    }
+}
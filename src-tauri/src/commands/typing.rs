@@ -0,0 +1,328 @@
+// commands/typing.rs
+// Typing awareness for activity-based features ("start recording when the
+// user starts typing"). PRIVACY: only the *number* of key presses is counted.
+// Which keys were pressed is never read, stored or sent anywhere - the only
+// thing that leaves this module is a count per interval.
+
+use log::info;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+// "typing-activity" goes out at most once per interval
+const TYPING_INTERVAL: Duration = Duration::from_millis(1000);
+
+// ============================================================================
+// Start / Stop Typing Monitor Commands
+// ============================================================================
+// Emits "typing-activity" with { count, interval_ms } for every interval in
+// which keys were pressed. Returns false if the monitor was already running.
+#[tauri::command]
+pub fn start_typing_monitor_command(
+    app_handle: AppHandle,
+    monitor: State<TypingMonitor>,
+) -> Result<bool, String> {
+    if monitor.is_running() {
+        return Ok(false);
+    }
+
+    let source = platform_source()?;
+    let started = monitor.start(source, TYPING_INTERVAL, move |count| {
+        let _ = app_handle.emit_all(
+            "typing-activity",
+            TypingActivity {
+                count,
+                interval_ms: TYPING_INTERVAL.as_millis() as u64,
+            },
+        );
+    });
+
+    if started {
+        info!("⌨️  Typing monitor started (counts only, no key contents)");
+    }
+    Ok(started)
+}
+
+// Returns false if the monitor wasn't running
+#[tauri::command]
+pub fn stop_typing_monitor_command(monitor: State<TypingMonitor>) -> Result<bool, String> {
+    let stopped = monitor.stop();
+    if stopped {
+        info!("⌨️  Typing monitor stopped");
+    }
+    Ok(stopped)
+}
+
+// ============================================================================
+// Keystroke Sources
+// ============================================================================
+// Something that can tell how many keys were pressed since it was last asked
+pub trait KeystrokeSource: Send {
+    fn take_count(&mut self) -> u64;
+}
+
+// Windows: a low-level keyboard hook on its own thread (the hook needs a
+// message loop). The hook callback only bumps a counter.
+#[cfg(target_os = "windows")]
+fn platform_source() -> Result<Box<dyn KeystrokeSource>, String> {
+    Ok(Box::new(windows_hook::HookSource::install()?))
+}
+
+// macOS: the system already keeps a running key-down count, so there's no
+// hook (and no accessibility permission) needed - it's polled per interval
+#[cfg(target_os = "macos")]
+fn platform_source() -> Result<Box<dyn KeystrokeSource>, String> {
+    Ok(Box::new(mac_counter::KeyDownCounter::new()))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn platform_source() -> Result<Box<dyn KeystrokeSource>, String> {
+    Err("Typing monitor is not supported on Linux.".to_string())
+}
+
+#[cfg(target_os = "windows")]
+mod windows_hook {
+    use super::KeystrokeSource;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::mpsc;
+    use std::thread::JoinHandle;
+    use windows_sys::Win32::Foundation::{LPARAM, LRESULT, WPARAM};
+    use windows_sys::Win32::System::LibraryLoader::GetModuleHandleW;
+    use windows_sys::Win32::System::Threading::GetCurrentThreadId;
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        CallNextHookEx, GetMessageW, PostThreadMessageW, SetWindowsHookExW, UnhookWindowsHookEx,
+        HC_ACTION, MSG, WH_KEYBOARD_LL, WM_KEYDOWN, WM_QUIT, WM_SYSKEYDOWN,
+    };
+
+    static KEY_DOWNS: AtomicU64 = AtomicU64::new(0);
+
+    pub struct HookSource {
+        thread_id: u32,
+        thread: Option<JoinHandle<()>>,
+        last: u64,
+    }
+
+    unsafe extern "system" fn keyboard_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+        // lparam (the key data) is deliberately never looked at
+        if code == HC_ACTION as i32
+            && (wparam == WM_KEYDOWN as usize || wparam == WM_SYSKEYDOWN as usize)
+        {
+            KEY_DOWNS.fetch_add(1, Ordering::Relaxed);
+        }
+        CallNextHookEx(0, code, wparam, lparam)
+    }
+
+    impl HookSource {
+        pub fn install() -> Result<Self, String> {
+            let (ready_tx, ready_rx) = mpsc::channel();
+
+            let thread = std::thread::spawn(move || unsafe {
+                let hook = SetWindowsHookExW(
+                    WH_KEYBOARD_LL,
+                    Some(keyboard_proc),
+                    GetModuleHandleW(std::ptr::null()),
+                    0,
+                );
+                let _ = ready_tx.send((GetCurrentThreadId(), hook != 0));
+                if hook == 0 {
+                    return;
+                }
+
+                let mut msg: MSG = std::mem::zeroed();
+                while GetMessageW(&mut msg, 0, 0, 0) > 0 {}
+                UnhookWindowsHookEx(hook);
+            });
+
+            match ready_rx.recv() {
+                Ok((thread_id, true)) => Ok(HookSource {
+                    thread_id,
+                    thread: Some(thread),
+                    last: KEY_DOWNS.load(Ordering::Relaxed),
+                }),
+                _ => {
+                    let _ = thread.join();
+                    Err("Failed to install keyboard hook".to_string())
+                }
+            }
+        }
+    }
+
+    impl KeystrokeSource for HookSource {
+        fn take_count(&mut self) -> u64 {
+            let total = KEY_DOWNS.load(Ordering::Relaxed);
+            let count = total.wrapping_sub(self.last);
+            self.last = total;
+            count
+        }
+    }
+
+    impl Drop for HookSource {
+        fn drop(&mut self) {
+            unsafe { PostThreadMessageW(self.thread_id, WM_QUIT, 0, 0) };
+            if let Some(thread) = self.thread.take() {
+                let _ = thread.join();
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod mac_counter {
+    use super::KeystrokeSource;
+
+    const HID_SYSTEM_STATE: i32 = 1; // kCGEventSourceStateHIDSystemState
+    const KEY_DOWN: u32 = 10; // kCGEventKeyDown
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGEventSourceCounterForEventType(state_id: i32, event_type: u32) -> u32;
+    }
+
+    pub struct KeyDownCounter {
+        last: u32,
+    }
+
+    impl KeyDownCounter {
+        pub fn new() -> Self {
+            KeyDownCounter {
+                last: unsafe { CGEventSourceCounterForEventType(HID_SYSTEM_STATE, KEY_DOWN) },
+            }
+        }
+    }
+
+    impl KeystrokeSource for KeyDownCounter {
+        fn take_count(&mut self) -> u64 {
+            let total = unsafe { CGEventSourceCounterForEventType(HID_SYSTEM_STATE, KEY_DOWN) };
+            let count = total.wrapping_sub(self.last);
+            self.last = total;
+            count as u64
+        }
+    }
+}
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TypingActivity {
+    pub count: u64,
+    pub interval_ms: u64,
+}
+
+// Managed state: the running monitor thread, if any. Dropping the sender
+// stops it; the source (and with it any hook) goes away with the thread.
+#[derive(Default)]
+pub struct TypingMonitor {
+    running: Mutex<Option<(Sender<()>, JoinHandle<()>)>>,
+}
+
+impl TypingMonitor {
+    pub fn is_running(&self) -> bool {
+        self.running.lock().unwrap().is_some()
+    }
+
+    // Returns false (and drops `source`) if already running
+    pub fn start<F>(
+        &self,
+        mut source: Box<dyn KeystrokeSource>,
+        interval: Duration,
+        on_activity: F,
+    ) -> bool
+    where
+        F: Fn(u64) + Send + 'static,
+    {
+        let mut running = self.running.lock().unwrap();
+        if running.is_some() {
+            return false;
+        }
+
+        let (stop_tx, stop_rx) = mpsc::channel::<()>();
+        let thread = std::thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stop_rx.recv_timeout(interval) {
+                let count = source.take_count();
+                if count > 0 {
+                    on_activity(count);
+                }
+            }
+        });
+
+        *running = Some((stop_tx, thread));
+        true
+    }
+
+    // Waits for the monitor thread to finish, so the hook is gone on return
+    pub fn stop(&self) -> bool {
+        let Some((stop_tx, thread)) = self.running.lock().unwrap().take() else {
+            return false;
+        };
+        drop(stop_tx);
+        let _ = thread.join();
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::sync::Arc;
+
+    // Reports 3 key presses per interval; records when it's dropped
+    struct FakeSource {
+        dropped: Arc<AtomicBool>,
+    }
+
+    impl KeystrokeSource for FakeSource {
+        fn take_count(&mut self) -> u64 {
+            3
+        }
+    }
+
+    impl Drop for FakeSource {
+        fn drop(&mut self) {
+            self.dropped.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn monitor_starts_and_stops_cleanly() {
+        let monitor = TypingMonitor::default();
+        let dropped = Arc::new(AtomicBool::new(false));
+        let total = Arc::new(AtomicU64::new(0));
+
+        let source = Box::new(FakeSource {
+            dropped: dropped.clone(),
+        });
+        let total_clone = total.clone();
+        assert!(
+            monitor.start(source, Duration::from_millis(10), move |count| {
+                total_clone.fetch_add(count, Ordering::SeqCst);
+            })
+        );
+        assert!(monitor.is_running());
+
+        // A second start is refused and its source released
+        let second_dropped = Arc::new(AtomicBool::new(false));
+        let second = Box::new(FakeSource {
+            dropped: second_dropped.clone(),
+        });
+        assert!(!monitor.start(second, Duration::from_millis(10), |_| {}));
+        assert!(second_dropped.load(Ordering::SeqCst));
+
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(monitor.stop());
+        assert!(!monitor.is_running());
+        assert!(dropped.load(Ordering::SeqCst));
+
+        // Nothing is counted after stop() returns
+        let counted = total.load(Ordering::SeqCst);
+        assert!(counted > 0 && counted.is_multiple_of(3));
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(total.load(Ordering::SeqCst), counted);
+
+        assert!(!monitor.stop());
+    }
+}
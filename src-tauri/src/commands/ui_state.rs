@@ -0,0 +1,59 @@
+// commands/ui_state.rs
+// Generic key/value store for frontend layout state that must survive cache clears
+
+use crate::state::ui_state::{load_ui_state, save_ui_state, UiState};
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+
+// ============================================================================
+// Save UI State Command
+// ============================================================================
+// Passing `null` as the value removes the key.
+#[tauri::command]
+pub fn save_ui_state_command(
+    app_handle: AppHandle,
+    state: State<Mutex<Option<UiState>>>,
+    key: String,
+    value: serde_json::Value,
+) -> Result<(), String> {
+    if key.trim().is_empty() {
+        return Err("UI state key must not be empty".to_string());
+    }
+
+    let mut ui_state_lock = state.lock().unwrap();
+    let mut ui_state = match ui_state_lock.take() {
+        Some(ui_state) => ui_state,
+        None => load_ui_state(&app_handle)?,
+    };
+
+    if value.is_null() {
+        ui_state.remove(&key);
+    } else {
+        ui_state.insert(key, value);
+    }
+
+    // Keep the cache even if the write fails so the session stays consistent
+    let result = save_ui_state(&app_handle, &ui_state);
+    *ui_state_lock = Some(ui_state);
+    result
+}
+
+// ============================================================================
+// Load UI State Command
+// ============================================================================
+#[tauri::command]
+pub fn load_ui_state_command(
+    app_handle: AppHandle,
+    state: State<Mutex<Option<UiState>>>,
+    key: String,
+) -> Result<Option<serde_json::Value>, String> {
+    let mut ui_state_lock = state.lock().unwrap();
+
+    if ui_state_lock.is_none() {
+        *ui_state_lock = Some(load_ui_state(&app_handle)?);
+    }
+
+    Ok(ui_state_lock
+        .as_ref()
+        .and_then(|ui_state| ui_state.get(&key).cloned()))
+}
@@ -0,0 +1,121 @@
+// commands/views.rs
+// Basic multi-view management. Tauri v1 has no hook for a page's new-window
+// requests, so popups and target="_blank" links on provider pages are routed
+// back into the main window by an injected script instead of vanishing.
+
+use crate::commands::whispr::is_dashboard_url;
+use log::{error, info};
+use tauri::{AppHandle, Manager, Window};
+
+// Runs once per document; wraps window.open and catches _blank link clicks.
+// window.open returns null like a blocked popup: handing back `window` would
+// let the page close() or document.write() itself thinking it's the popup.
+const POPUP_ROUTER_SCRIPT: &str = r#"(function () {
+  if (window.__whisprPopupRouter) return;
+  window.__whisprPopupRouter = true;
+
+  window.open = function (url) {
+    if (url) window.location.href = new URL(url, window.location.href).href;
+    return null;
+  };
+
+  document.addEventListener('click', function (event) {
+    var link = event.target && event.target.closest ? event.target.closest('a[target]') : null;
+    if (!link || !link.href || link.target === '_self' || link.target === '_top') return;
+    event.preventDefault();
+    window.location.href = link.href;
+  }, true);
+})()"#;
+
+// ============================================================================
+// List Views Command
+// ============================================================================
+// The app's Tauri windows. Routed popups replace the main window's page
+// rather than opening a view of their own, so they aren't listed.
+#[tauri::command]
+pub fn list_views_command(app_handle: AppHandle) -> Result<Vec<ViewInfo>, String> {
+    let mut views: Vec<ViewInfo> = app_handle
+        .windows()
+        .into_iter()
+        .map(|(id, window)| ViewInfo {
+            id,
+            title: window.title().unwrap_or_default(),
+            url: window.url().to_string(),
+            visible: window.is_visible().unwrap_or(false),
+            focused: window.is_focused().unwrap_or(false),
+        })
+        .collect();
+
+    views.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(views)
+}
+
+// ============================================================================
+// Focus View Command
+// ============================================================================
+#[tauri::command]
+pub fn focus_view_command(app_handle: AppHandle, id: String) -> Result<(), String> {
+    let window = get_view(&app_handle, &id)?;
+
+    window
+        .show()
+        .map_err(|e| format!("Failed to show view: {}", e))?;
+    window
+        .set_focus()
+        .map_err(|e| format!("Failed to focus view: {}", e))?;
+
+    info!("🪟 Focused view: {}", id);
+    Ok(())
+}
+
+// ============================================================================
+// Close View Command
+// ============================================================================
+// The main window can't be closed this way - use close_app_command.
+#[tauri::command]
+pub fn close_view_command(app_handle: AppHandle, id: String) -> Result<(), String> {
+    if id == "main" {
+        return Err("The main view can't be closed".to_string());
+    }
+
+    get_view(&app_handle, &id)?
+        .close()
+        .map_err(|e| format!("Failed to close view: {}", e))?;
+
+    info!("🪟 Closed view: {}", id);
+    Ok(())
+}
+
+// ============================================================================
+// Popup Routing
+// ============================================================================
+// Called from the page-load hook in main.rs for every new document.
+pub fn install_popup_router(window: &Window, url: &str) {
+    match url::Url::parse(url) {
+        Ok(url) if !is_dashboard_url(&url) => {}
+        _ => return,
+    }
+
+    if let Err(e) = window.eval(POPUP_ROUTER_SCRIPT) {
+        error!("❌ Failed to install popup router: {}", e);
+    }
+}
+
+fn get_view(app_handle: &AppHandle, id: &str) -> Result<Window, String> {
+    app_handle
+        .get_window(id)
+        .ok_or_else(|| format!("View not found: {}", id))
+}
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ViewInfo {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub visible: bool,
+    pub focused: bool,
+}
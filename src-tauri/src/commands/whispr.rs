@@ -1,8 +1,51 @@
 // commands/whispr.rs
 // UPDATED: Navigate main window URL (like Electron) instead of creating new window
 
-use log::info;
-use tauri::Manager;
+use crate::commands::network::http_client;
+use crate::commands::window::require_main_window;
+use crate::limits::check_prompt;
+use crate::types::WhisperSettings;
+use log::{error, info, warn};
+use std::collections::HashSet;
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{Manager, State};
+
+// ============================================================================
+// AI Providers
+// ============================================================================
+// llm setting value -> chat URL. Keep in sync with the Dashboard provider list.
+pub const PROVIDERS: [(&str, &str); 5] = [
+    ("chatgpt", "https://chatgpt.com"),
+    ("grok", "https://grok.com"),
+    ("deepseek", "https://chat.deepseek.com"),
+    ("gemini", "https://gemini.google.com/app"),
+    ("perplexity", "https://www.perplexity.ai"),
+];
+
+// Chat URL for an llm setting value
+pub fn provider_url(llm: &str) -> Option<&'static str> {
+    PROVIDERS
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(llm.trim()))
+        .map(|&(_, url)| url)
+}
+
+// llm key of the provider serving `url`, matched on the host
+pub fn provider_for_url(url: &url::Url) -> Option<&'static str> {
+    let host = url.host_str()?;
+    PROVIDERS
+        .iter()
+        .find(|(_, provider_url)| {
+            url::Url::parse(provider_url).is_ok_and(|p| p.host_str() == Some(host))
+        })
+        .map(|&(llm, _)| llm)
+}
+
+const PROVIDER_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Oldest pages fall off the back history beyond this
+const MAX_HISTORY_ENTRIES: usize = 50;
 
 // ============================================================================
 // Launch Whispr Mode - Navigate main window to AI provider URL
@@ -13,21 +56,79 @@ pub fn launch_whispr_mode_command(
     url: String,
 ) -> Result<(), String> {
     info!("🚀 Launching Whispr mode with URL: {}", url);
+    check_provider_allowed(&url, &custom_provider_hosts(&app_handle))?;
 
-    if let Some(main_window) = app_handle.get_window("main") {
-        // Navigate the main window to the AI provider URL
-        main_window
-            .eval(&format!("window.location.href = '{}'", url))
-            .map_err(|e| format!("Failed to navigate window: {}", e))?;
-        
-        info!("✅ Main window navigated to: {}", url);
-    } else {
-        return Err("Main window not found".to_string());
-    }
+    navigate_to_provider(&app_handle, &url)?;
+    app_handle.state::<NavigationHistory>().push(&url);
+    Ok(())
+}
+
+fn navigate_to_provider(app_handle: &tauri::AppHandle, url: &str) -> Result<(), String> {
+    let main_window = require_main_window(app_handle)?;
+
+    // Start timing before the navigation so the measurement covers the whole load
+    app_handle.state::<ProviderLoadTracker>().start(url);
+    let _ = main_window.emit("provider-load-started", url);
+
+    // Navigate the main window to the AI provider URL
+    let confirm = should_confirm_navigation(app_handle, &main_window);
+    navigate_main_window(app_handle, &main_window, url, confirm)
+        .map_err(|e| format!("Failed to navigate window: {}", e))?;
+
+    info!("✅ Main window navigated to: {}", url);
 
     Ok(())
 }
 
+// Only https pages on a built-in provider's host, or on a host the user added
+// to custom_provider_hosts - so a compromised frontend can't send the window
+// anywhere else
+fn check_provider_allowed(url: &str, custom_hosts: &[String]) -> Result<(), String> {
+    let allowed = url::Url::parse(url).is_ok_and(|url| {
+        url.scheme() == "https"
+            && (provider_for_url(&url).is_some()
+                || url.host_str().is_some_and(|host| {
+                    custom_hosts
+                        .iter()
+                        .any(|custom| custom.trim().eq_ignore_ascii_case(host))
+                }))
+    });
+
+    if !allowed {
+        warn!("🚫 Refusing to navigate to non-provider URL: {}", url);
+        return Err("provider not allowed".to_string());
+    }
+    Ok(())
+}
+
+fn custom_provider_hosts(app_handle: &tauri::AppHandle) -> Vec<String> {
+    let settings = app_handle.state::<Mutex<Option<WhisperSettings>>>();
+    let hosts = match settings.lock().unwrap().as_ref() {
+        Some(settings) => settings.custom_provider_hosts.clone(),
+        None => crate::state::settings::load_settings(app_handle)
+            .map(|settings| settings.custom_provider_hosts)
+            .unwrap_or_default(),
+    };
+    hosts
+}
+
+// ============================================================================
+// Launch Current Provider - the one picked in settings.llm
+// ============================================================================
+#[tauri::command]
+pub fn launch_current_provider_command(
+    app_handle: tauri::AppHandle,
+    state: State<Mutex<Option<WhisperSettings>>>,
+) -> Result<(), String> {
+    let llm = match state.lock().unwrap().as_ref() {
+        Some(settings) => settings.llm.clone(),
+        None => crate::state::settings::load_settings(&app_handle)?.llm,
+    };
+    let url = provider_url(&llm).ok_or_else(|| format!("Unknown provider '{}'", llm))?;
+
+    launch_whispr_mode_command(app_handle, url.to_string())
+}
+
 // ============================================================================
 // Navigate to Dashboard - Reload main window to show React app
 // ============================================================================
@@ -35,32 +136,551 @@ pub fn launch_whispr_mode_command(
 pub fn navigate_to_dashboard_command(app_handle: tauri::AppHandle) -> Result<(), String> {
     info!("🏠 Navigating back to dashboard");
 
-    if let Some(main_window) = app_handle.get_window("main") {
-        // Navigate back to the React app (reload the app URL)
-        #[cfg(debug_assertions)]
-        let app_url = "http://localhost:1420";
-        
-        #[cfg(not(debug_assertions))]
-        let app_url = "tauri://localhost";
-        
-        main_window
-            .eval(&format!("window.location.href = '{}'", app_url))
-            .map_err(|e| format!("Failed to navigate to dashboard: {}", e))?;
-        
-        info!("✅ Main window navigated back to dashboard");
-    } else {
-        return Err("Main window not found".to_string());
+    let main_window = require_main_window(&app_handle)?;
+
+    // Navigate back to the React app (reload the app URL)
+    #[cfg(debug_assertions)]
+    let app_url = "http://localhost:1420";
+    
+    #[cfg(not(debug_assertions))]
+    let app_url = "tauri://localhost";
+
+    // Leaving the provider - don't time the dashboard load
+    app_handle.state::<ProviderLoadTracker>().reset();
+    // The dashboard is where the back history starts
+    app_handle.state::<NavigationHistory>().clear();
+    
+    let confirm = should_confirm_navigation(&app_handle, &main_window);
+    navigate_main_window(&app_handle, &main_window, app_url, confirm)
+        .map_err(|e| format!("Failed to navigate to dashboard: {}", e))?;
+    
+    info!("✅ Main window navigated back to dashboard");
+
+    Ok(())
+}
+
+// ============================================================================
+// Navigate Back - previous provider page, or the dashboard
+// ============================================================================
+#[tauri::command]
+pub fn navigate_back_command(
+    app_handle: tauri::AppHandle,
+    history: State<NavigationHistory>,
+) -> Result<(), String> {
+    match history.back()? {
+        Some(url) => {
+            info!("⬅️  Navigating back to: {}", url);
+            navigate_to_provider(&app_handle, &url)
+        }
+        None => navigate_to_dashboard_command(app_handle),
     }
+}
+
+// ============================================================================
+// Main Window Navigation - "navigate" event first, eval as the fallback
+// ============================================================================
+// The dashboard's router takes a "navigate" event for the dashboard itself and
+// acknowledges it with ack_navigation_command. Provider pages have no router,
+// and a router can't load a provider (an external https page), so those go
+// through eval straight away. So does a dashboard that doesn't answer within
+// NAVIGATE_ACK_TIMEOUT (still loading, older frontend).
+const NAVIGATE_ACK_TIMEOUT: Duration = Duration::from_millis(1000);
 
+fn navigate_main_window(
+    app_handle: &tauri::AppHandle,
+    window: &tauri::Window,
+    url: &str,
+    confirm_unsent_input: bool,
+) -> Result<(), String> {
+    if !routes_in_frontend(&window.url(), url) {
+        info!("🧭 Navigating via eval: {}", url);
+        return window
+            .eval(&navigation_script(url, confirm_unsent_input))
+            .map_err(|e| e.to_string());
+    }
+
+    let id = app_handle.state::<NavigationAcks>().begin();
+    window
+        .emit(
+            "navigate",
+            NavigatePayload {
+                id,
+                url: url.to_string(),
+            },
+        )
+        .map_err(|e| e.to_string())?;
+
+    // Wait off the main thread - the ack comes in through it
+    let app_handle = app_handle.clone();
+    let window = window.clone();
+    let url = url.to_string();
+    std::thread::spawn(move || {
+        let acks = app_handle.state::<NavigationAcks>();
+        if acks.wait(id, NAVIGATE_ACK_TIMEOUT) {
+            info!("🧭 Navigated via the frontend router: {}", url);
+            return;
+        }
+
+        warn!("🧭 Navigation not acknowledged, using eval: {}", url);
+        if let Err(e) = window.eval(&navigation_script(&url, confirm_unsent_input)) {
+            error!("❌ Failed to navigate window: {}", e);
+        }
+    });
     Ok(())
 }
 
+fn routes_in_frontend(current: &url::Url, target: &str) -> bool {
+    is_dashboard_url(current) && url::Url::parse(target).is_ok_and(|url| is_dashboard_url(&url))
+}
+
+#[tauri::command]
+pub fn ack_navigation_command(acks: State<NavigationAcks>, id: u64) {
+    acks.ack(id);
+}
+
 // ============================================================================
-// Get Current Route - Not really applicable with this approach
+// Unsent Input Confirmation
+// ============================================================================
+// With confirm_navigation_with_input on, leaving a provider page that has text
+// in its prompt box asks first. The dashboard isn't loaded while a provider is
+// shown, so the prompt has to come from the provider page itself.
+const UNSENT_INPUT_MESSAGE: &str = "You have an unsent prompt. Leave this page and discard it?";
+
+fn should_confirm_navigation(app_handle: &tauri::AppHandle, window: &tauri::Window) -> bool {
+    let settings = app_handle.state::<Mutex<Option<WhisperSettings>>>();
+    let enabled = match settings.lock().unwrap().as_ref() {
+        Some(settings) => settings.confirm_navigation_with_input,
+        None => crate::state::settings::load_settings(app_handle)
+            .map(|settings| settings.confirm_navigation_with_input)
+            .unwrap_or(false),
+    };
+
+    // Only provider pages - the dashboard's own forms don't count
+    enabled && !is_dashboard_url(&window.url())
+}
+
+pub fn is_dashboard_url(url: &url::Url) -> bool {
+    url.scheme() == "tauri" || matches!(url.host_str(), Some("localhost" | "tauri.localhost"))
+}
+
+// Values go in as JSON string literals, so a quote or backslash in the URL
+// can't end the string and run as script
+fn navigation_script(url: &str, confirm_unsent_input: bool) -> String {
+    let url = js_string(url);
+    if !confirm_unsent_input {
+        return format!("window.location.href = {}", url);
+    }
+
+    format!(
+        r#"(function () {{
+  var fields = document.querySelectorAll('textarea, [contenteditable="true"], input[type="text"]');
+  var hasInput = Array.prototype.some.call(fields, function (el) {{
+    var text = el.isContentEditable ? el.innerText : el.value;
+    return text && text.trim().length > 0;
+  }});
+  if (hasInput && !window.confirm({})) return;
+  window.location.href = {};
+}})()"#,
+        js_string(UNSENT_INPUT_MESSAGE),
+        url
+    )
+}
+
+fn js_string(value: &str) -> String {
+    // Serializing a str can't fail
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+// ============================================================================
+// Prompt Injection - put text into the provider's prompt box
+// ============================================================================
+// Providers use either a <textarea> (set through the native setter so React
+// notices) or a contenteditable editor (insertText keeps the editor's state).
+pub fn inject_prompt(window: &tauri::Window, text: &str) -> Result<(), String> {
+    check_prompt("Prompt", text)?;
+    let text =
+        serde_json::to_string(text).map_err(|e| format!("Failed to encode prompt: {}", e))?;
+
+    let script = format!(
+        r#"(function () {{
+  var text = {};
+  var el = document.querySelector('textarea, [contenteditable="true"]');
+  if (!el) return;
+  el.focus();
+  if (el.isContentEditable) {{
+    document.execCommand('selectAll', false);
+    document.execCommand('insertText', false, text);
+  }} else {{
+    Object.getOwnPropertyDescriptor(HTMLTextAreaElement.prototype, 'value').set.call(el, text);
+    el.dispatchEvent(new Event('input', {{ bubbles: true }}));
+  }}
+}})()"#,
+        text
+    );
+
+    window
+        .eval(&script)
+        .map_err(|e| format!("Failed to inject prompt: {}", e))
+}
+
+// ============================================================================
+// Get Current Route - the page the main window is showing
+// ============================================================================
+// The full URL: a provider page, or the dashboard (see is_dashboard_url).
+// Read from the webview, so it follows links clicked inside the provider too.
+#[tauri::command]
+pub fn get_current_route_command(app_handle: tauri::AppHandle) -> Result<String, String> {
+    let main_window = require_main_window(&app_handle)?;
+    Ok(main_window.url().to_string())
+}
+
+// ============================================================================
+// Provider Load Time - ms between provider-load-started and provider-load-finished
 // ============================================================================
 #[tauri::command]
-pub fn get_current_route_command(_app_handle: tauri::AppHandle) -> Result<String, String> {
-    // Since we're navigating away from the React app entirely,
-    // this becomes less meaningful. Return a placeholder.
-    Ok("/".to_string())
-}
\ No newline at end of file
+pub fn get_last_provider_load_ms_command(tracker: State<ProviderLoadTracker>) -> Option<u64> {
+    *tracker.last_ms.lock().unwrap()
+}
+
+// Called from the page-load hook in main.rs. Tauri fires it when its init
+// script runs in the new document, so for remote providers this measures the
+// time until the provider page starts executing, not until it is fully idle.
+pub fn finish_provider_load(window: &tauri::Window, url: &str) {
+    let tracker = window.state::<ProviderLoadTracker>();
+    let Some(load_ms) = tracker.finish() else {
+        return;
+    };
+
+    info!("⏱️  Provider page loaded in {}ms: {}", load_ms, url);
+    let _ = window.emit(
+        "provider-load-finished",
+        ProviderLoadFinished {
+            url: url.to_string(),
+            load_ms,
+        },
+    );
+}
+
+// ============================================================================
+// Verify Providers - HEAD each provider URL to catch moved/broken domains
+// ============================================================================
+#[tauri::command]
+pub async fn verify_providers_command(
+    app_handle: tauri::AppHandle,
+    state: State<'_, Mutex<Option<WhisperSettings>>>,
+) -> Result<Vec<ProviderStatus>, String> {
+    let proxy_url = match state.lock().unwrap().as_ref() {
+        Some(settings) => settings.proxy_url.clone(),
+        None => crate::state::settings::load_settings(&app_handle)?.proxy_url,
+    };
+    verify_providers(proxy_url).await
+}
+
+pub async fn verify_providers(proxy_url: Option<String>) -> Result<Vec<ProviderStatus>, String> {
+    let client = http_client(PROVIDER_CHECK_TIMEOUT, proxy_url.as_deref())?;
+
+    // Check all providers concurrently
+    let checks: Vec<_> = PROVIDERS
+        .iter()
+        .map(|&(llm, url)| tauri::async_runtime::spawn(check_provider(client.clone(), llm, url)))
+        .collect();
+
+    let mut results = Vec::with_capacity(checks.len());
+    for check in checks {
+        let status = check
+            .await
+            .map_err(|e| format!("Provider check failed to run: {}", e))?;
+
+        if status.reachable {
+            info!(
+                "🌐 {} reachable ({}ms, HTTP {})",
+                status.llm,
+                status.latency_ms,
+                status.status.unwrap_or_default()
+            );
+        } else {
+            warn!(
+                "🌐 {} NOT reachable: {}",
+                status.llm,
+                status.error.as_deref().unwrap_or("unknown error")
+            );
+        }
+        results.push(status);
+    }
+
+    Ok(results)
+}
+
+async fn check_provider(
+    client: reqwest::Client,
+    llm: &'static str,
+    url: &'static str,
+) -> ProviderStatus {
+    let started = Instant::now();
+    let response = client.head(url).send().await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    match response {
+        // Anything below 5xx means the server is there (some reject HEAD with 405)
+        Ok(response) => ProviderStatus {
+            llm: llm.to_string(),
+            url: url.to_string(),
+            reachable: !response.status().is_server_error(),
+            status: Some(response.status().as_u16()),
+            latency_ms,
+            error: None,
+        },
+        Err(e) => ProviderStatus {
+            llm: llm.to_string(),
+            url: url.to_string(),
+            reachable: false,
+            status: None,
+            latency_ms,
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProviderStatus {
+    pub llm: String,
+    pub url: String,
+    pub reachable: bool,
+    pub status: Option<u16>,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProviderLoadFinished {
+    pub url: String,
+    pub load_ms: u64,
+}
+
+// Tracks the in-flight provider navigation and the last completed measurement
+#[derive(Default)]
+pub struct ProviderLoadTracker {
+    started: Mutex<Option<Instant>>,
+    last_ms: Mutex<Option<u64>>,
+}
+
+impl ProviderLoadTracker {
+    // Each navigation clears the previous result
+    fn start(&self, url: &str) {
+        *self.started.lock().unwrap() = Some(Instant::now());
+        *self.last_ms.lock().unwrap() = None;
+        info!("⏱️  Timing provider load: {}", url);
+    }
+
+    fn reset(&self) {
+        *self.started.lock().unwrap() = None;
+        *self.last_ms.lock().unwrap() = None;
+    }
+
+    // Returns None if no provider navigation is pending
+    fn finish(&self) -> Option<u64> {
+        let started = self.started.lock().unwrap().take()?;
+        let load_ms = started.elapsed().as_millis() as u64;
+        *self.last_ms.lock().unwrap() = Some(load_ms);
+        Some(load_ms)
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct NavigatePayload {
+    pub id: u64,
+    pub url: String,
+}
+
+// Managed state: "navigate" events sent and the ones the frontend acknowledged
+#[derive(Default)]
+pub struct NavigationAcks {
+    state: Mutex<PendingNavigations>,
+    acked: Condvar,
+}
+
+#[derive(Default)]
+struct PendingNavigations {
+    next_id: u64,
+    acked: HashSet<u64>,
+}
+
+impl NavigationAcks {
+    fn begin(&self) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        state.next_id += 1;
+        state.next_id
+    }
+
+    fn ack(&self, id: u64) {
+        self.state.lock().unwrap().acked.insert(id);
+        self.acked.notify_all();
+    }
+
+    // True if `id` was acknowledged in time
+    fn wait(&self, id: u64, timeout: Duration) -> bool {
+        let state = self.state.lock().unwrap();
+        let (mut state, _) = self
+            .acked
+            .wait_timeout_while(state, timeout, |state| !state.acked.contains(&id))
+            .unwrap();
+        state.acked.remove(&id)
+    }
+}
+
+// Managed state: provider pages opened through launch_whispr_mode_command,
+// oldest first. The last entry is the page being shown; an empty history
+// means the dashboard.
+#[derive(Default)]
+pub struct NavigationHistory {
+    entries: Mutex<Vec<String>>,
+}
+
+impl NavigationHistory {
+    fn push(&self, url: &str) {
+        let mut entries = self.entries.lock().unwrap();
+        // Reopening the page that's already shown isn't a step to go back over
+        if entries.last().map(String::as_str) == Some(url) {
+            return;
+        }
+        entries.push(url.to_string());
+        if entries.len() > MAX_HISTORY_ENTRIES {
+            entries.remove(0);
+        }
+    }
+
+    fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    // Drops the current page and returns the one before it (None for the
+    // dashboard). Err if already on the dashboard.
+    fn back(&self) -> Result<Option<String>, String> {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.pop().is_none() {
+            return Err("No page to go back to".to_string());
+        }
+        Ok(entries.last().cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn navigation_falls_back_without_an_ack() {
+        let acks = std::sync::Arc::new(NavigationAcks::default());
+
+        let id = acks.begin();
+        let frontend = acks.clone();
+        std::thread::spawn(move || frontend.ack(id));
+        assert!(acks.wait(id, Duration::from_secs(5)));
+
+        // Nobody answers this one; a late ack for it doesn't count for the next
+        let unanswered = acks.begin();
+        assert!(!acks.wait(unanswered, Duration::from_millis(20)));
+        let next = acks.begin();
+        assert_ne!(next, unanswered);
+        assert!(!acks.wait(next, Duration::from_millis(20)));
+    }
+
+    #[test]
+    fn only_dashboard_pages_go_through_the_router() {
+        let dashboard = url::Url::parse("tauri://localhost").unwrap();
+        let provider = url::Url::parse("https://chatgpt.com/c/1").unwrap();
+
+        assert!(routes_in_frontend(&dashboard, "tauri://localhost"));
+        assert!(!routes_in_frontend(&dashboard, "https://chatgpt.com"));
+        assert!(!routes_in_frontend(&provider, "tauri://localhost"));
+        assert!(!routes_in_frontend(&provider, "https://grok.com"));
+    }
+
+    #[test]
+    fn back_walks_the_history_to_the_dashboard() {
+        let history = NavigationHistory::default();
+        assert!(history.back().is_err());
+
+        history.push("https://chatgpt.com");
+        history.push("https://chatgpt.com/c/1");
+        history.push("https://chatgpt.com/c/1");
+        history.push("https://grok.com");
+
+        assert_eq!(
+            history.back().unwrap().as_deref(),
+            Some("https://chatgpt.com/c/1")
+        );
+        assert_eq!(
+            history.back().unwrap().as_deref(),
+            Some("https://chatgpt.com")
+        );
+        assert_eq!(history.back().unwrap(), None);
+        assert!(history.back().is_err());
+
+        for i in 0..MAX_HISTORY_ENTRIES + 5 {
+            history.push(&format!("https://grok.com/{}", i));
+        }
+        assert_eq!(history.entries.lock().unwrap().len(), MAX_HISTORY_ENTRIES);
+    }
+
+    #[test]
+    fn llm_settings_map_to_provider_urls() {
+        assert_eq!(provider_url("chatgpt"), Some("https://chatgpt.com"));
+        assert_eq!(provider_url(" Grok "), Some("https://grok.com"));
+        assert_eq!(provider_url("bard"), None);
+
+        // Every mapped URL passes the launch allowlist
+        for (llm, _) in PROVIDERS {
+            assert!(check_provider_allowed(provider_url(llm).unwrap(), &[]).is_ok());
+        }
+    }
+
+    #[test]
+    fn only_provider_hosts_are_allowed() {
+        let custom = vec!["chat.mistral.ai".to_string()];
+
+        for url in [
+            "https://chatgpt.com/c/123",
+            "https://gemini.google.com/app",
+            "https://www.perplexity.ai/search?q=x",
+            "https://CHAT.MISTRAL.AI/chat",
+        ] {
+            assert!(check_provider_allowed(url, &custom).is_ok(), "{}", url);
+        }
+
+        for url in [
+            "http://chatgpt.com",
+            "https://chatgpt.com.evil.example",
+            "https://evil.example/?chatgpt.com",
+            "javascript:alert(1)",
+            "not a url",
+        ] {
+            assert_eq!(
+                check_provider_allowed(url, &custom).unwrap_err(),
+                "provider not allowed"
+            );
+        }
+        assert!(check_provider_allowed("https://chat.mistral.ai", &[]).is_err());
+    }
+
+    #[test]
+    fn navigation_urls_cannot_break_out_of_the_script() {
+        let url = r#"https://chatgpt.com/?q=it's'; alert(1); '\"#;
+
+        for confirm in [false, true] {
+            let script = navigation_script(url, confirm);
+            let literal = serde_json::to_string(url).unwrap();
+            assert!(script.contains(&format!("window.location.href = {}", literal)));
+
+            // The literal reads back as exactly the URL
+            let start = script.find(&literal).unwrap();
+            let parsed: String =
+                serde_json::from_str(&script[start..start + literal.len()]).unwrap();
+            assert_eq!(parsed, url);
+        }
+    }
+}
@@ -2,15 +2,78 @@
 // Window management commands - replaces your Electron window/app IPC handlers
 // TAURI V1 COMPATIBLE
 
-use tauri::{AppHandle, Window};
+use crate::commands::auth::reveal_in_file_manager;
+use crate::commands::dnd::allow_interruption;
+use crate::commands::logs::active_log_file;
+use crate::commands::settings::{try_update_settings, update_settings};
+use crate::commands::shortcuts::global_shortcuts_supported;
+use crate::state::settings::{load_settings, save_settings};
+use crate::state::settings_saver::SettingsSaver;
+use crate::types::{WhisperSettings, WindowGeometry};
+use log::{error, info, warn};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State, Window};
 
 // ============================================================================
 // IMPORTANT NOTE ABOUT TAURI V1 LIMITATIONS
 // ============================================================================
 // Tauri v1 has fewer window management APIs than Electron or Tauri v2.
-// Some features (like set_opacity, set_focusable) are not available.
+// Some features (like blur) are not available; others (like opacity and
+// focusable) are done with platform-specific native code instead.
 // We'll implement what's available and note limitations.
 
+// ============================================================================
+// Main Window
+// ============================================================================
+// For callers that can't work without the main window, so a closed window
+// always fails the same way
+pub fn require_main_window(app_handle: &AppHandle) -> Result<Window, String> {
+    app_handle
+        .get_window("main")
+        .ok_or_else(|| "Main window not found".to_string())
+}
+
+// Builds the main window again from its tauri.conf.json entry and puts it back
+// where the settings say, the way startup does. It starts hidden; the caller
+// shows it.
+pub fn recreate_main_window(app_handle: &AppHandle) -> Result<Window, String> {
+    let config = app_handle
+        .config()
+        .tauri
+        .windows
+        .iter()
+        .find(|config| config.label == "main")
+        .cloned()
+        .ok_or_else(|| "Main window config not found".to_string())?;
+    let window = tauri::WindowBuilder::from_config(app_handle, config)
+        .build()
+        .map_err(|e| format!("Failed to create main window: {}", e))?;
+
+    let state = app_handle.state::<Mutex<Option<WhisperSettings>>>();
+    let settings = match state.lock().as_deref() {
+        Ok(Some(settings)) => settings.clone(),
+        _ => WhisperSettings::default(),
+    };
+
+    // Subclassed first, as at startup, so the ExStyles survive the show
+    watch_display_changes(&window)?;
+    apply_window_level(&window, window_level_for(&settings))?;
+    if !settings.focusable {
+        set_focusable(&window, false)?;
+    }
+    restore_window_size(&window, &settings)?;
+    watch_window_size(&window);
+    restore_window_position(&window, &settings)?;
+    ensure_window_visible(&window)?;
+
+    info!("🪟 Main window recreated");
+    Ok(window)
+}
+
 // ============================================================================
 // Close App Command
 // ============================================================================
@@ -25,71 +88,614 @@ pub fn close_app_command(window: Window) -> Result<(), String> {
 // ============================================================================
 // Restart App Command
 // ============================================================================
+// restart() exits the process without a RunEvent::Exit, so a debounced
+// settings save has to be written here or it's lost
 #[tauri::command]
 pub fn restart_app_command(app_handle: AppHandle) -> Result<(), String> {
+    if let Err(e) = app_handle.state::<SettingsSaver>().flush() {
+        error!("❌ Failed to flush settings before restart: {}", e);
+    }
     app_handle.restart();
     Ok(())
 }
 
 // ============================================================================
-// Set Opacity Command - LIMITED SUPPORT IN V1
+// Force Quit Command
+// ============================================================================
+// Emergency exit: kills webview/helper processes the app spawned (they can
+// orphan after the main window closes) before exiting. Only descendants of
+// this process are touched. On macOS WebKit helpers are owned by launchd, so
+// there is usually nothing to kill there.
+#[tauri::command]
+pub fn force_quit_command(app_handle: AppHandle) -> Result<(), String> {
+    let current =
+        sysinfo::get_current_pid().map_err(|e| format!("Failed to get current pid: {}", e))?;
+
+    let mut system = sysinfo::System::new();
+    system.refresh_processes();
+
+    let processes: Vec<(u32, Option<u32>)> = system
+        .processes()
+        .iter()
+        .map(|(pid, process)| (pid.as_u32(), process.parent().map(|p| p.as_u32())))
+        .collect();
+
+    // Children before parents so nothing gets re-parented mid-way
+    let descendants = descendant_pids(&processes, current.as_u32());
+    for pid in descendants.into_iter().rev() {
+        let Some(process) = system.process(sysinfo::Pid::from_u32(pid)) else {
+            continue;
+        };
+        if process.kill() {
+            info!("💀 Terminated child process {} ({})", pid, process.name());
+        } else {
+            warn!(
+                "⚠️  Failed to terminate child process {} ({})",
+                pid,
+                process.name()
+            );
+        }
+    }
+
+    info!("👋 Force quitting");
+    app_handle.exit(0);
+    Ok(())
+}
+
+// All pids below `root` in the (pid, parent pid) list, parents first
+fn descendant_pids(processes: &[(u32, Option<u32>)], root: u32) -> Vec<u32> {
+    let mut descendants = vec![root];
+    let mut i = 0;
+    while i < descendants.len() {
+        let parent = descendants[i];
+        for &(pid, ppid) in processes {
+            if ppid == Some(parent) && !descendants.contains(&pid) {
+                descendants.push(pid);
+            }
+        }
+        i += 1;
+    }
+    descendants.remove(0);
+    descendants
+}
+
+// ============================================================================
+// Set Opacity Command
 // ============================================================================
-// NOTE: Tauri v1 doesn't have set_opacity() on all platforms.
-// We'll return an informative error for now.
+// Native whole-window opacity (0.0 - 1.0): a layered window on Windows,
+// NSWindow alphaValue on macOS. Tauri v1 has no API for it.
 #[tauri::command]
-pub fn set_opacity_command(_window: Window, opacity: f64) -> Result<(), String> {
-    if opacity < 0.0 || opacity > 1.0 {
+pub fn set_opacity_command(window: Window, opacity: f64) -> Result<(), String> {
+    check_opacity(opacity)?;
+    set_opacity_now(&window, opacity)
+}
+
+fn check_opacity(opacity: f64) -> Result<(), String> {
+    if !(0.0..=1.0).contains(&opacity) {
         return Err("Opacity must be between 0.0 and 1.0".to_string());
     }
+    Ok(())
+}
+
+// opacity from the settings in memory (the default before they're loaded)
+pub fn saved_opacity(app_handle: &AppHandle) -> f64 {
+    let state = app_handle.state::<Mutex<Option<WhisperSettings>>>();
+    let opacity = match state.lock().as_deref() {
+        Ok(Some(settings)) => settings.opacity,
+        _ => WhisperSettings::default().opacity,
+    };
+    opacity
+}
+
+// ============================================================================
+// Opacity Steps
+// ============================================================================
+// Change the saved opacity by the opacity_step setting, never below
+// MIN_OPACITY so the window can't disappear entirely, and return the new value.
+// Applied natively on Windows and macOS; on Linux the frontend applies it.
+const MIN_OPACITY: f64 = 0.1;
+
+#[tauri::command]
+pub fn increase_opacity_command(
+    app_handle: AppHandle,
+    window: Window,
+    state: State<Mutex<Option<WhisperSettings>>>,
+) -> Result<f64, String> {
+    step_opacity(&app_handle, &window, &state, 1.0)
+}
+
+#[tauri::command]
+pub fn decrease_opacity_command(
+    app_handle: AppHandle,
+    window: Window,
+    state: State<Mutex<Option<WhisperSettings>>>,
+) -> Result<f64, String> {
+    step_opacity(&app_handle, &window, &state, -1.0)
+}
+
+fn step_opacity(
+    app_handle: &AppHandle,
+    window: &Window,
+    state: &Mutex<Option<WhisperSettings>>,
+    direction: f64,
+) -> Result<f64, String> {
+    let settings = try_update_settings(app_handle, state, |settings| {
+        let opacity = stepped_opacity(settings.opacity, settings.opacity_step * direction);
+        if NATIVE_OPACITY {
+            set_opacity_now(window, opacity)?;
+        }
+        settings.opacity = opacity;
+        Ok(())
+    })?;
+
+    info!("🔆 Opacity set to {}", settings.opacity);
+    Ok(settings.opacity)
+}
+
+// Rounded to hundredths, so repeated steps don't drift (0.1 + 0.2 != 0.3)
+fn stepped_opacity(opacity: f64, delta: f64) -> f64 {
+    let stepped = ((opacity + delta) * 100.0).round() / 100.0;
+    stepped.clamp(MIN_OPACITY, 1.0)
+}
+
+#[cfg(target_os = "windows")]
+fn set_native_opacity(window: &Window, opacity: f64) -> Result<(), String> {
+    let hwnd = window
+        .hwnd()
+        .map_err(|e| format!("Failed to get window handle: {}", e))?
+        .0;
+
+    let alpha = (opacity * 255.0).round() as u8;
+    window.state::<ExStyles>().set_alpha(hwnd, alpha)
+}
+
+#[cfg(target_os = "macos")]
+fn set_native_opacity(window: &Window, opacity: f64) -> Result<(), String> {
+    use cocoa::appkit::NSWindow;
+    use cocoa::base::id;
+
+    let ns_window = window
+        .ns_window()
+        .map_err(|e| format!("Failed to get native window: {}", e))? as usize;
+
+    // AppKit must be called from the main thread
+    window
+        .run_on_main_thread(move || unsafe {
+            (ns_window as id).setAlphaValue_(opacity);
+        })
+        .map_err(|e| format!("Failed to set window opacity: {}", e))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn set_native_opacity(_window: &Window, _opacity: f64) -> Result<(), String> {
+    Err("Window opacity is not supported on Linux. Use CSS opacity on your React components instead.".to_string())
+}
+
+// ============================================================================
+// Fade Animation
+// ============================================================================
+// show_window_command and hide_window_command ramp the native opacity over
+// the fade_duration_ms setting, one FADE_FRAME at a time on a background
+// thread. Any other opacity change (another show or hide, set_opacity_command)
+// stops a fade where it is, so a quick toggle turns around instead of leaving
+// the window half-faded.
+const FADE_FRAME: Duration = Duration::from_millis(15);
+
+const NATIVE_OPACITY: bool = cfg!(any(target_os = "windows", target_os = "macos"));
+
+fn fade_duration(window: &Window) -> Duration {
+    let state = window.state::<Mutex<Option<WhisperSettings>>>();
+    let millis = match state.lock().as_deref() {
+        Ok(Some(settings)) => settings.fade_duration_ms,
+        _ => WhisperSettings::default().fade_duration_ms,
+    };
+    Duration::from_millis(millis.into())
+}
+
+fn fade_frames(duration: Duration) -> u32 {
+    let frames = duration.as_millis() / FADE_FRAME.as_millis();
+    u32::try_from(frames).unwrap_or(u32::MAX).max(1)
+}
+
+// The opacity `frame` frames into a fade of `frames`
+fn fade_opacity(from: f64, to: f64, frame: u32, frames: u32) -> f64 {
+    from + (to - from) * f64::from(frame) / f64::from(frames)
+}
+
+// Stops any fade and sets the opacity the window is shown at
+fn set_opacity_now(window: &Window, opacity: f64) -> Result<(), String> {
+    let fade = window.state::<WindowFade>();
+    let mut levels = fade.levels.lock().unwrap();
+    fade.generation.fetch_add(1, Ordering::SeqCst);
+
+    set_native_opacity(window, opacity)?;
+    *levels = FadeLevels {
+        current: Some(opacity),
+        shown: Some(opacity),
+    };
+    Ok(())
+}
+
+fn fade_in(window: &Window, opacity: f64) -> Result<(), String> {
+    let fade = window.state::<WindowFade>();
+    let mut levels = fade.levels.lock().unwrap();
+    let generation = fade.generation.fetch_add(1, Ordering::SeqCst) + 1;
+
+    // Hidden windows start from transparent; one still fading out turns
+    // around where it is. On Windows the show can't make it opaque for a
+    // frame: the layered style and its alpha are ExStyles.
+    let visible = window.is_visible().unwrap_or(false);
+    let from = if visible {
+        levels.current.unwrap_or(1.0)
+    } else {
+        0.0
+    };
+    set_native_opacity(window, from)?;
+    *levels = FadeLevels {
+        current: Some(from),
+        shown: Some(opacity),
+    };
+    drop(levels);
+
+    window
+        .show()
+        .map_err(|e| format!("Failed to show window: {}", e))?;
+    spawn_fade(window, generation, from, opacity, None);
+    Ok(())
+}
+
+fn fade_out(window: &Window) {
+    let fade = window.state::<WindowFade>();
+    let levels = fade.levels.lock().unwrap();
+    let generation = fade.generation.fetch_add(1, Ordering::SeqCst) + 1;
+
+    let from = levels.current.unwrap_or(1.0);
+    let shown = levels.shown.unwrap_or(1.0);
+    drop(levels);
+
+    spawn_fade(window, generation, from, 0.0, Some(shown));
+}
+
+// Ramps from `from` to `to`. With `hide_at`, the window is then hidden and
+// put back at that opacity for whatever shows it next.
+fn spawn_fade(window: &Window, generation: u64, from: f64, to: f64, hide_at: Option<f64>) {
+    let window = window.clone();
+    let frames = fade_frames(fade_duration(&window));
+
+    std::thread::spawn(move || {
+        let fade = window.state::<WindowFade>();
+        for frame in 1..=frames {
+            std::thread::sleep(FADE_FRAME);
+            let mut levels = fade.levels.lock().unwrap();
+            if fade.generation.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            let opacity = fade_opacity(from, to, frame, frames);
+            if let Err(e) = set_native_opacity(&window, opacity) {
+                warn!("⚠️  Fade stopped: {}", e);
+                break;
+            }
+            levels.current = Some(opacity);
+        }
+
+        let Some(shown) = hide_at else {
+            return;
+        };
+        let mut levels = fade.levels.lock().unwrap();
+        if fade.generation.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        if let Err(e) = window.hide() {
+            warn!("⚠️  Failed to hide window after fading out: {}", e);
+        }
+        if set_native_opacity(&window, shown).is_ok() {
+            levels.current = Some(shown);
+        }
+    });
+}
+
+#[cfg(target_os = "windows")]
+fn ex_style(hwnd: isize) -> u32 {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{GetWindowLongW, GWL_EXSTYLE};
+
+    unsafe { GetWindowLongW(hwnd, GWL_EXSTYLE) as u32 }
+}
+
+// Turns an extended window style bit on or off
+#[cfg(target_os = "windows")]
+fn set_ex_style(hwnd: isize, flag: u32, enabled: bool) {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{SetWindowLongW, GWL_EXSTYLE};
+
+    let style = ex_style(hwnd);
+    let style = if enabled { style | flag } else { style & !flag };
+    unsafe { SetWindowLongW(hwnd, GWL_EXSTYLE, style as i32) };
+}
+
+// ============================================================================
+// Extended Window Styles (Windows)
+// ============================================================================
+// tao rebuilds GWL_EXSTYLE from its own flags on every show, hide and
+// set_always_on_top, dropping any bit it doesn't know about. So the bits the
+// app needs on top go through ExStyles, which remembers them, and the main
+// window's subclass (watch_display_changes) puts them back whenever tao
+// rewrites the styles.
+#[cfg(target_os = "windows")]
+impl ExStyles {
+    // Turns `flag` on or off, and keeps it that way
+    fn set(&self, hwnd: isize, flag: u32, enabled: bool) {
+        if enabled {
+            self.kept.fetch_or(flag, Ordering::SeqCst);
+        } else {
+            self.kept.fetch_and(!flag, Ordering::SeqCst);
+        }
+        if (ex_style(hwnd) & flag != 0) != enabled {
+            set_ex_style(hwnd, flag, enabled);
+        }
+    }
+
+    // Makes the window layered at `alpha` (255 is opaque)
+    fn set_alpha(&self, hwnd: isize, alpha: u8) -> Result<(), String> {
+        use windows_sys::Win32::UI::WindowsAndMessaging::{
+            SetLayeredWindowAttributes, LWA_ALPHA, WS_EX_LAYERED,
+        };
+
+        self.alpha.store(alpha, Ordering::SeqCst);
+        self.set(hwnd, WS_EX_LAYERED, true);
+        if unsafe { SetLayeredWindowAttributes(hwnd, 0, alpha, LWA_ALPHA) } == 0 {
+            return Err("Failed to set window opacity".to_string());
+        }
+        Ok(())
+    }
+
+    // WM_STYLECHANGING: the kept bits are added to whatever is being set.
+    // WM_STYLECHANGED: a window that just became layered isn't drawn until it
+    // has attributes, so it gets the last alpha (tao's click-through makes a
+    // window layered too).
+    unsafe fn on_style_change(&self, hwnd: isize, msg: u32, wparam: usize, lparam: isize) {
+        use windows_sys::Win32::UI::WindowsAndMessaging::{
+            SetLayeredWindowAttributes, GWL_EXSTYLE, LWA_ALPHA, STYLESTRUCT, WM_STYLECHANGED,
+            WM_STYLECHANGING, WS_EX_LAYERED,
+        };
+
+        if wparam as i32 != GWL_EXSTYLE {
+            return;
+        }
+        let styles = &mut *(lparam as *mut STYLESTRUCT);
+        if msg == WM_STYLECHANGING {
+            styles.styleNew |= self.kept.load(Ordering::SeqCst);
+        } else if msg == WM_STYLECHANGED
+            && styles.styleNew & WS_EX_LAYERED != 0
+            && styles.styleOld & WS_EX_LAYERED == 0
+        {
+            let alpha = self.alpha.load(Ordering::SeqCst);
+            SetLayeredWindowAttributes(hwnd, 0, alpha, LWA_ALPHA);
+        }
+    }
+}
+
+// ============================================================================
+// Set Ignore Cursor Events Command (click-through)
+// ============================================================================
+// Lets clicks go through the whole window to the app underneath while it stays
+// visible. Replaces any pass-through regions, which would flip it back.
+#[tauri::command]
+pub fn set_ignore_cursor_events_command(
+    window: Window,
+    regions: State<PassthroughRegions>,
+    ignore: bool,
+) -> Result<(), String> {
+    if regions.is_active() {
+        regions.rects.lock().unwrap().clear();
+        regions.generation.fetch_add(1, Ordering::SeqCst);
+        info!("🖱️  Pass-through regions cleared");
+    }
 
-    // Tauri v1 limitation: opacity control not available
-    // Options:
-    // 1. Upgrade to Tauri v2
-    // 2. Use CSS opacity on the web content instead
-    // 3. Use platform-specific workarounds
+    set_click_through(&window, ignore)?;
+    regions.whole_window.store(ignore, Ordering::SeqCst);
+    info!("🖱️  Click-through {}", if ignore { "on" } else { "off" });
+    Ok(())
+}
 
-    Err("Opacity control is not available in Tauri v1. Use CSS opacity on your React components instead, or upgrade to Tauri v2.".to_string())
+// Tauri's own version everywhere, as the pass-through poller uses. On Windows
+// that leaves WS_EX_TRANSPARENT to tao's flags, so its style rewrites keep it.
+pub fn set_click_through(window: &Window, ignore: bool) -> Result<(), String> {
+    window
+        .set_ignore_cursor_events(ignore)
+        .map_err(|e| format!("Failed to set click-through: {}", e))
 }
 
 // ============================================================================
 // Move Window Command
 // ============================================================================
+// Deltas are in the units of the logical_pixels setting
 #[tauri::command]
 pub fn move_window_command(window: Window, delta_x: i32, delta_y: i32) -> Result<(), String> {
-    let position = window
-        .outer_position()
-        .map_err(|e| format!("Failed to get window position: {}", e))?;
+    move_window_by(&window, delta_x, delta_y)
+}
 
-    let new_x = position.x + delta_x;
-    let new_y = position.y + delta_y;
+// Shared by the command and the movement shortcuts. A move that ends within
+// SNAP_THRESHOLD_PX of an edge of the window's monitor, or would cross one,
+// stops flush with that edge; the next move in that direction goes past it.
+pub fn move_window_by(window: &Window, delta_x: i32, delta_y: i32) -> Result<(), String> {
+    let scale = pixel_scale(window);
+    let (delta_x, delta_y) = (to_physical(delta_x, scale), to_physical(delta_y, scale));
+
+    let current = window_geometry(window)?;
+    let monitors: Vec<WindowGeometry> = window
+        .available_monitors()
+        .map_err(|e| format!("Failed to list monitors: {}", e))?
+        .iter()
+        .map(monitor_geometry)
+        .collect();
+    let (x, y) = snapped_move(current, delta_x, delta_y, &monitors);
 
     window
-        .set_position(tauri::Position::Physical(tauri::PhysicalPosition {
-            x: new_x,
-            y: new_y,
-        }))
+        .set_position(tauri::Position::Physical(tauri::PhysicalPosition { x, y }))
         .map_err(|e| format!("Failed to set window position: {}", e))?;
 
     Ok(())
 }
 
+const SNAP_THRESHOLD_PX: i64 = 15;
+
+fn snapped_move(
+    window: WindowGeometry,
+    delta_x: i32,
+    delta_y: i32,
+    monitors: &[WindowGeometry],
+) -> (i32, i32) {
+    let x = window.x.saturating_add(delta_x);
+    let y = window.y.saturating_add(delta_y);
+    let Some(monitor) = monitors
+        .iter()
+        .filter(|&&monitor| overlap_area(window, monitor) > 0)
+        .max_by_key(|&&monitor| overlap_area(window, monitor))
+    else {
+        return (x, y);
+    };
+
+    let snap = |from: i32, to: i32, len: u32, start: i32, available: u32| {
+        if from == to {
+            return to;
+        }
+        let (from, to) = (from as i64, to as i64);
+        let flush_start = start as i64;
+        let flush_end = start as i64 + available as i64 - len as i64;
+        for edge in [flush_start, flush_end] {
+            let crosses = (from - edge).signum() != (to - edge).signum();
+            if from != edge && (crosses || (to - edge).abs() <= SNAP_THRESHOLD_PX) {
+                return edge as i32;
+            }
+        }
+        to as i32
+    };
+
+    (
+        snap(window.x, x, window.width, monitor.x, monitor.width),
+        snap(window.y, y, window.height, monitor.y, monitor.height),
+    )
+}
+
+// ============================================================================
+// Move To Next Monitor
+// ============================================================================
+// Monitors are taken left to right (then top to bottom), wrapping around. The
+// window keeps its relative spot, pulled fully onto the new monitor. With a
+// single monitor nothing moves.
+#[tauri::command]
+pub fn move_to_next_monitor_command(window: Window) -> Result<(), String> {
+    let current = window_geometry(&window)?;
+    let monitors: Vec<WindowGeometry> = window
+        .available_monitors()
+        .map_err(|e| format!("Failed to list monitors: {}", e))?
+        .iter()
+        .map(monitor_geometry)
+        .collect();
+    let Some((x, y)) = next_monitor_position(current, monitors) else {
+        return Ok(());
+    };
+
+    window
+        .set_position(tauri::Position::Physical(tauri::PhysicalPosition { x, y }))
+        .map_err(|e| format!("Failed to move window: {}", e))?;
+
+    info!("🖥️  Window moved to the next monitor at ({}, {})", x, y);
+    Ok(())
+}
+
+fn next_monitor_position(
+    window: WindowGeometry,
+    mut monitors: Vec<WindowGeometry>,
+) -> Option<(i32, i32)> {
+    if monitors.len() < 2 {
+        return None;
+    }
+    monitors.sort_by_key(|monitor| (monitor.x, monitor.y));
+
+    // The monitor with most of the window (the first one if it's on none)
+    let (index, from) = monitors
+        .iter()
+        .enumerate()
+        .rev()
+        .max_by_key(|(_, &monitor)| overlap_area(window, monitor))?;
+    let to = monitors[(index + 1) % monitors.len()];
+
+    let relative = |position: i32, from_start: i32, from_len: u32, to_start: i32, to_len: u32| {
+        let fraction = (position as i64 - from_start as i64) as f64 / from_len.max(1) as f64;
+        (to_start as i64 + (fraction * to_len as f64).round() as i64) as i32
+    };
+    let moved = WindowGeometry {
+        x: relative(window.x, from.x, from.width, to.x, to.width),
+        y: relative(window.y, from.y, from.height, to.y, to.height),
+        ..window
+    };
+    Some(clamp_into(moved, to))
+}
+
 // ============================================================================
 // Hide Window Command
 // ============================================================================
+// Fades out first if fade_duration_ms is set (the command returns right away)
 #[tauri::command]
 pub fn hide_window_command(window: Window) -> Result<(), String> {
+    if NATIVE_OPACITY && !fade_duration(&window).is_zero() && window.is_visible().unwrap_or(false) {
+        fade_out(&window);
+        return Ok(());
+    }
+    window
+        .hide()
+        .map_err(|e| format!("Failed to hide window: {}", e))?;
+    Ok(())
+}
+
+// ============================================================================
+// Panic Hide
+// ============================================================================
+// Hides the window and has the frontend wipe whatever it was showing. The hide
+// is queued first so the content is never on screen while the frontend clears
+// it. panic_restore_command brings the (now empty) window back.
+#[tauri::command]
+pub fn panic_hide_command(window: Window) -> Result<(), String> {
     window
         .hide()
         .map_err(|e| format!("Failed to hide window: {}", e))?;
+    // No fade, and none left running that would show it again
+    if NATIVE_OPACITY {
+        let _ = set_opacity_now(&window, saved_opacity(&window.app_handle()));
+    }
+    window
+        .emit("clear-content", ())
+        .map_err(|e| format!("Failed to clear content: {}", e))?;
+    info!("🚨 Panic hide");
+    Ok(())
+}
+
+#[tauri::command]
+pub fn panic_restore_command(window: Window) -> Result<(), String> {
+    window
+        .show()
+        .map_err(|e| format!("Failed to show window: {}", e))?;
+    let _ = window.set_focus();
     Ok(())
 }
 
 // ============================================================================
 // Show Window Command
 // ============================================================================
+// Shown at `opacity`, as set_opacity_command would set it, fading in if
+// fade_duration_ms is set. Linux has no native opacity, so there the window
+// is just shown.
 #[tauri::command]
-pub fn show_window_command(window: Window, _opacity: f64) -> Result<(), String> {
-    // Note: opacity parameter is ignored in v1 (not supported)
+pub fn show_window_command(window: Window, opacity: f64) -> Result<(), String> {
+    check_opacity(opacity)?;
+    if NATIVE_OPACITY && !fade_duration(&window).is_zero() {
+        return fade_in(&window, opacity);
+    }
+    // Before showing, so the window never appears at the wrong opacity. On
+    // Windows the layered style is one of the ExStyles, so the show keeps it.
+    if NATIVE_OPACITY {
+        set_opacity_now(&window, opacity)?;
+    }
     window
         .show()
         .map_err(|e| format!("Failed to show window: {}", e))?;
@@ -117,88 +723,1517 @@ pub fn toggle_window_visibility_command(window: Window, opacity: f64) -> Result<
 // ============================================================================
 // Set Always On Top
 // ============================================================================
+// Saved as the always_on_top setting. Turning it on goes back to the saved
+// window_level (floating if that was normal).
 #[tauri::command]
-pub fn set_always_on_top_command(window: Window, always_on_top: bool) -> Result<(), String> {
-    window
-        .set_always_on_top(always_on_top)
-        .map_err(|e| format!("Failed to set always on top: {}", e))?;
-    Ok(())
+pub fn set_always_on_top_command(
+    app_handle: AppHandle,
+    window: Window,
+    state: State<Mutex<Option<WhisperSettings>>>,
+    always_on_top: bool,
+) -> Result<(), String> {
+    set_always_on_top(&app_handle, &window, &state, always_on_top)
 }
 
-// ============================================================================
-// Get App Version
-// ============================================================================
+// Flips always-on-top and returns whether it's now on
 #[tauri::command]
-pub fn get_app_version_command(app_handle: AppHandle) -> Result<String, String> {
-    let version = app_handle.package_info().version.to_string();
-    Ok(version)
+pub fn toggle_always_on_top_command(
+    app_handle: AppHandle,
+    window: Window,
+    state: State<Mutex<Option<WhisperSettings>>>,
+) -> Result<bool, String> {
+    let always_on_top = !current_window_level(&window).is_topmost();
+    set_always_on_top(&app_handle, &window, &state, always_on_top)?;
+    info!("📌 Always on top: {}", always_on_top);
+    Ok(always_on_top)
+}
+
+fn set_always_on_top(
+    app_handle: &AppHandle,
+    window: &Window,
+    state: &Mutex<Option<WhisperSettings>>,
+    always_on_top: bool,
+) -> Result<(), String> {
+    let settings = update_settings(app_handle, state, |settings| {
+        settings.always_on_top = always_on_top;
+        if always_on_top && settings.window_level == WindowLevel::Normal.as_str() {
+            settings.window_level = WindowLevel::Floating.as_str().to_string();
+        }
+    })?;
+    apply_window_level(window, window_level_for(&settings))
+}
+
+// The level the settings ask for
+pub fn window_level_for(settings: &WhisperSettings) -> WindowLevel {
+    if !settings.always_on_top {
+        return WindowLevel::Normal;
+    }
+    WindowLevel::parse(&settings.window_level).unwrap_or(WindowLevel::Floating)
 }
 
 // ============================================================================
-// Delete Cache Command
+// Set Window Level
 // ============================================================================
+// A finer-grained always-on-top. Exact NSWindow levels on macOS; Windows only
+// has one topmost z-band for regular apps, and on Linux anything above
+// "normal" is approximated with plain always-on-top.
 #[tauri::command]
-pub fn delete_cache_command(window: Window) -> Result<String, String> {
-    let clear_script = r#"
-        (function() {
-            try {
-                localStorage.clear();
-                sessionStorage.clear();
-                
-                if (window.indexedDB && window.indexedDB.databases) {
-                    indexedDB.databases().then(dbs => {
-                        dbs.forEach(db => {
-                            if (db.name) indexedDB.deleteDatabase(db.name);
-                        });
-                    });
-                }
-                
-                return 'success';
-            } catch (e) {
-                return 'error: ' + e.message;
-            }
-        })()
-    "#;
+pub fn set_window_level_command(
+    app_handle: AppHandle,
+    window: Window,
+    state: State<Mutex<Option<WhisperSettings>>>,
+    level: String,
+) -> Result<(), String> {
+    let level = WindowLevel::parse(&level)?;
+
+    apply_window_level(&window, level)?;
+    update_settings(&app_handle, &state, |settings| {
+        settings.window_level = level.as_str().to_string();
+        settings.always_on_top = level.is_topmost();
+    })?;
+
+    info!("🪟 Window level set to {}", level.as_str());
+    Ok(())
+}
+
+// Also records the level (so temporary changes like flash_to_front_command can
+// restore it) and cancels a pending flash restore, since this level is newer
+pub fn apply_window_level(window: &Window, level: WindowLevel) -> Result<(), String> {
+    if let Some(levels) = window.try_state::<WindowLevelState>() {
+        *levels.current.lock().unwrap() = level;
+        levels.flash.lock().unwrap().take();
+    }
 
+    set_native_level(window, level)?;
+    watch_topmost(window, level);
+    Ok(())
+}
+
+// The level last applied with apply_window_level
+pub fn current_window_level(window: &Window) -> WindowLevel {
     window
-        .eval(clear_script)
-        .map_err(|e| format!("Failed to clear cache: {}", e))?;
+        .try_state::<WindowLevelState>()
+        .map(|levels| *levels.current.lock().unwrap())
+        .unwrap_or_default()
+}
 
-    Ok("Cache cleared successfully".to_string())
+fn set_native_level(window: &Window, level: WindowLevel) -> Result<(), String> {
+    window
+        .set_always_on_top(level.is_topmost())
+        .map_err(|e| format!("Failed to set always on top: {}", e))?;
+
+    #[cfg(target_os = "macos")]
+    {
+        use cocoa::appkit::NSWindow;
+        use cocoa::base::id;
+
+        let ns_window = window
+            .ns_window()
+            .map_err(|e| format!("Failed to get native window: {}", e))?
+            as usize;
+        let ns_level = level.ns_window_level();
+
+        // AppKit must be called from the main thread
+        window
+            .run_on_main_thread(move || unsafe {
+                (ns_window as id).setLevel_(ns_level);
+            })
+            .map_err(|e| format!("Failed to set window level: {}", e))?;
+    }
+
+    Ok(())
 }
 
 // ============================================================================
-// Set Window Size
+// Flash To Front
 // ============================================================================
+// Briefly shows, focuses and raises the window (e.g. for a notification), then
+// puts it back at the level it had before. Flashing again restarts the timer.
+// Does nothing while Do Not Disturb is on.
 #[tauri::command]
-pub fn set_window_size_command(window: Window, width: u32, height: u32) -> Result<(), String> {
+pub fn flash_to_front_command(
+    window: Window,
+    levels: State<WindowLevelState>,
+    duration_ms: u32,
+) -> Result<(), String> {
+    if !allow_interruption(&window.app_handle(), "flash to front") {
+        return Ok(());
+    }
+
+    let restore_to = *levels.current.lock().unwrap();
+    let id = levels.next_flash_id.fetch_add(1, Ordering::SeqCst) + 1;
+
+    // Replacing the previous flash drops its sender, which cancels its timer
+    let (cancel_tx, cancel_rx) = mpsc::channel::<()>();
+    *levels.flash.lock().unwrap() = Some((id, cancel_tx));
+
     window
-        .set_size(tauri::Size::Physical(tauri::PhysicalSize { width, height }))
-        .map_err(|e| format!("Failed to set window size: {}", e))?;
+        .show()
+        .map_err(|e| format!("Failed to show window: {}", e))?;
+    window
+        .set_focus()
+        .map_err(|e| format!("Failed to focus window: {}", e))?;
+    set_native_level(&window, restore_to.max(WindowLevel::Top))?;
 
+    std::thread::spawn(move || {
+        let expired = matches!(
+            cancel_rx.recv_timeout(Duration::from_millis(duration_ms as u64)),
+            Err(RecvTimeoutError::Timeout)
+        );
+        if !expired {
+            return;
+        }
+
+        // Only restore if nothing replaced this flash in the meantime
+        let levels = window.state::<WindowLevelState>();
+        let mut flash = levels.flash.lock().unwrap();
+        if matches!(*flash, Some((flash_id, _)) if flash_id == id) {
+            flash.take();
+            drop(flash);
+            if let Err(e) = set_native_level(&window, restore_to) {
+                log::error!("❌ Failed to restore window level: {}", e);
+            }
+        }
+    });
+
+    info!(
+        "✨ Flashed window to front for {}ms (restoring {})",
+        duration_ms,
+        restore_to.as_str()
+    );
     Ok(())
 }
 
 // ============================================================================
-// Set Window Focusable - NOT AVAILABLE IN V1
+// Reassert Always On Top
 // ============================================================================
+// Re-applies the current window level if it's above normal. Returns whether
+// always-on-top had actually been lost (always false on Linux, where it can't
+// be read back).
 #[tauri::command]
-pub fn set_window_focusable_command(_window: Window, _focusable: bool) -> Result<(), String> {
-    // Tauri v1 doesn't have set_focusable()
-    // This would require Tauri v2 or platform-specific code
-    Err("set_focusable is not available in Tauri v1. This feature requires Tauri v2.".to_string())
+pub fn reassert_always_on_top_command(window: Window) -> Result<bool, String> {
+    reassert_topmost(&window)
+}
+
+fn reassert_topmost(window: &Window) -> Result<bool, String> {
+    let levels = window.state::<WindowLevelState>();
+    let level = *levels.current.lock().unwrap();
+    // A flash has the window even higher for now
+    if !level.is_topmost() || levels.flash.lock().unwrap().is_some() {
+        return Ok(false);
+    }
+
+    let lost = topmost_lost(window, level) == Some(true);
+    set_native_level(window, level)?;
+    if lost {
+        info!("📌 Always-on-top was lost, restored {}", level.as_str());
+    }
+    Ok(lost)
+}
+
+// Every topmost_reassert_secs while the level is above normal. Applying a new
+// level replaces the watcher (dropping its sender stops it), so turning
+// always-on-top off stops it right away.
+fn watch_topmost(window: &Window, level: WindowLevel) {
+    let Some(watcher) = window.try_state::<TopmostWatcher>() else {
+        return;
+    };
+    let state = window.state::<Mutex<Option<WhisperSettings>>>();
+    let secs = match state.lock().as_deref() {
+        Ok(Some(settings)) => settings.topmost_reassert_secs,
+        _ => WhisperSettings::default().topmost_reassert_secs,
+    };
+
+    let mut stop = watcher.stop.lock().unwrap();
+    stop.take();
+    if !level.is_topmost() || secs == 0 {
+        return;
+    }
+    let (stop_tx, stop_rx) = mpsc::channel::<()>();
+    *stop = Some(stop_tx);
+
+    let window = window.clone();
+    let interval = Duration::from_secs(secs.into());
+    std::thread::spawn(move || {
+        while let Err(RecvTimeoutError::Timeout) = stop_rx.recv_timeout(interval) {
+            if let Err(e) = reassert_topmost(&window) {
+                warn!("⚠️  Failed to reassert always-on-top: {}", e);
+            }
+        }
+    });
+}
+
+// None where the level can't be read back
+#[cfg(target_os = "windows")]
+fn topmost_lost(window: &Window, _level: WindowLevel) -> Option<bool> {
+    use windows_sys::Win32::UI::WindowsAndMessaging::WS_EX_TOPMOST;
+
+    let hwnd = window.hwnd().ok()?.0;
+    Some(ex_style(hwnd) & WS_EX_TOPMOST == 0)
+}
+
+#[cfg(target_os = "macos")]
+fn topmost_lost(window: &Window, level: WindowLevel) -> Option<bool> {
+    use cocoa::base::id;
+    use objc::{msg_send, sel, sel_impl};
+
+    let ns_window = window.ns_window().ok()? as usize;
+    let (tx, rx) = mpsc::channel();
+    // AppKit must be called from the main thread
+    window
+        .run_on_main_thread(move || unsafe {
+            let current: i64 = msg_send![ns_window as id, level];
+            let _ = tx.send(current);
+        })
+        .ok()?;
+    let current = rx.recv_timeout(Duration::from_secs(1)).ok()?;
+    Some(current < level.ns_window_level())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn topmost_lost(_window: &Window, _level: WindowLevel) -> Option<bool> {
+    None
 }
 
 // ============================================================================
-// TAURI V1 vs V2 NOTES:
+// Get App Version
 // ============================================================================
-// Missing in v1 (available in v2):
-// - set_opacity() - Window transparency
-// - set_focusable() - Whether window can be focused
-// - set_ignore_cursor_events() - Pass-through clicks
-//
-// Workarounds:
-// 1. Use CSS opacity instead of window opacity
+#[tauri::command]
+pub fn get_app_version_command(app_handle: AppHandle) -> Result<String, String> {
+    let version = app_handle.package_info().version.to_string();
+    Ok(version)
+}
+
+// ============================================================================
+// Get Build Info
+// ============================================================================
+// Commit and build time are embedded by build.rs so support can tell nightly
+// and PR builds apart from releases.
+#[tauri::command]
+pub fn get_build_info_command(app_handle: AppHandle) -> Result<BuildInfo, String> {
+    Ok(BuildInfo {
+        version: app_handle.package_info().version.to_string(),
+        commit: env!("VERGEN_GIT_SHA").to_string(),
+        build_timestamp: env!("VERGEN_BUILD_TIMESTAMP").to_string(),
+        target: env!("VERGEN_CARGO_TARGET_TRIPLE").to_string(),
+    })
+}
+
+// ============================================================================
+// Get System Locale
+// ============================================================================
+// Returns a BCP 47 style tag like "en-US", falling back to DEFAULT_LOCALE
+// when the OS doesn't tell us.
+#[tauri::command]
+pub fn get_system_locale_command() -> Result<String, String> {
+    Ok(system_locale())
+}
+
+const DEFAULT_LOCALE: &str = "en-US";
+
+pub fn system_locale() -> String {
+    native_locale()
+        .or_else(|| {
+            ["LC_ALL", "LC_MESSAGES", "LANG"]
+                .iter()
+                .filter_map(|var| std::env::var(var).ok())
+                .find_map(|value| normalize_locale(&value))
+        })
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string())
+}
+
+// "en_US.UTF-8" / "en_US@euro" -> "en-US"; "C" and "POSIX" carry no locale
+fn normalize_locale(raw: &str) -> Option<String> {
+    let tag = raw.split(['.', '@']).next()?.trim().replace('_', "-");
+    if tag.is_empty() || tag == "C" || tag == "POSIX" {
+        None
+    } else {
+        Some(tag)
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn native_locale() -> Option<String> {
+    use windows_sys::Win32::Globalization::GetUserDefaultLocaleName;
+
+    // LOCALE_NAME_MAX_LENGTH
+    let mut buffer = [0u16; 85];
+    let len = unsafe { GetUserDefaultLocaleName(buffer.as_mut_ptr(), buffer.len() as i32) };
+    if len <= 1 {
+        return None;
+    }
+    normalize_locale(&String::from_utf16_lossy(&buffer[..(len - 1) as usize]))
+}
+
+#[cfg(target_os = "macos")]
+fn native_locale() -> Option<String> {
+    use cocoa::base::{id, nil};
+    use objc::{class, msg_send, sel, sel_impl};
+    use std::ffi::CStr;
+
+    unsafe {
+        let locale: id = msg_send![class!(NSLocale), currentLocale];
+        let identifier: id = msg_send![locale, localeIdentifier];
+        if identifier == nil {
+            return None;
+        }
+        let utf8: *const std::os::raw::c_char = msg_send![identifier, UTF8String];
+        if utf8.is_null() {
+            return None;
+        }
+        normalize_locale(&CStr::from_ptr(utf8).to_string_lossy())
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn native_locale() -> Option<String> {
+    None
+}
+
+// ============================================================================
+// Open Log Directory Command
+// ============================================================================
+// Shows the folder with this run's log (and its rotated copies) in the OS file
+// manager, for attaching to a support request
+#[tauri::command]
+pub fn open_log_directory_command() -> Result<(), String> {
+    let log_file = active_log_file();
+    let log_dir = log_file
+        .parent()
+        .ok_or_else(|| format!("No folder for log file {}", log_file.display()))?;
+    reveal_in_file_manager(log_dir)
+}
+
+// ============================================================================
+// Delete Cache Command
+// ============================================================================
+#[tauri::command]
+pub fn delete_cache_command(window: Window) -> Result<String, String> {
+    let clear_script = r#"
+        (function() {
+            try {
+                localStorage.clear();
+                sessionStorage.clear();
+                
+                if (window.indexedDB && window.indexedDB.databases) {
+                    indexedDB.databases().then(dbs => {
+                        dbs.forEach(db => {
+                            if (db.name) indexedDB.deleteDatabase(db.name);
+                        });
+                    });
+                }
+                
+                return 'success';
+            } catch (e) {
+                return 'error: ' + e.message;
+            }
+        })()
+    "#;
+
+    window
+        .eval(clear_script)
+        .map_err(|e| format!("Failed to clear cache: {}", e))?;
+
+    Ok("Cache cleared successfully".to_string())
+}
+
+// ============================================================================
+// Set Window Size
+// ============================================================================
+// The size is kept between MIN_WINDOW_SIZE and the size of the window's
+// monitor, so a bad value can't leave the window too small to see or larger
+// than the screen. Sizes in and out are in the units of the logical_pixels
+// setting. Returns the size that was applied. The current size is saved as
+// screen_width/screen_height (see watch_window_size) and restored on launch.
+#[tauri::command]
+pub fn set_window_size_command(
+    window: Window,
+    width: u32,
+    height: u32,
+) -> Result<WindowSize, String> {
+    apply_window_size(&window, WindowSize { width, height })
+}
+
+// Back to the default size
+#[tauri::command]
+pub fn reset_window_size_command(window: Window) -> Result<WindowSize, String> {
+    apply_window_size(&window, saved_window_size(&WhisperSettings::default()))
+}
+
+// Startup: the size saved by watch_window_size
+pub fn restore_window_size(
+    window: &Window,
+    settings: &WhisperSettings,
+) -> Result<WindowSize, String> {
+    let size = apply_window_size(window, saved_window_size(settings))?;
+    info!("📐 Window size restored: {}x{}", size.width, size.height);
+    Ok(size)
+}
+
+// Saves every resize (from the OS or set_window_size_command), with the
+// disk write coalesced by the SettingsSaver. Minimized and maximized sizes
+// aren't kept.
+pub fn watch_window_size(window: &Window) {
+    let watched = window.clone();
+    window.on_window_event(move |event| {
+        let tauri::WindowEvent::Resized(size) = event else {
+            return;
+        };
+        if size.width == 0
+            || size.height == 0
+            || watched.is_minimized().unwrap_or(false)
+            || watched.is_maximized().unwrap_or(false)
+        {
+            return;
+        }
+
+        let size = WindowSize {
+            width: size.width,
+            height: size.height,
+        };
+        let size = scale_size(size, 1.0 / pixel_scale(&watched));
+
+        let state = watched.state::<Mutex<Option<WhisperSettings>>>();
+        let mut settings_lock = state.lock().unwrap();
+        let Some(settings) = settings_lock.as_mut() else {
+            return;
+        };
+        if !record_window_size(settings, size) {
+            return;
+        }
+        match watched.try_state::<SettingsSaver>() {
+            Some(saver) => saver.save(settings.clone()),
+            None => {
+                if let Err(e) = save_settings(&watched.app_handle(), settings) {
+                    warn!("⚠️  Failed to save window size: {}", e);
+                }
+            }
+        }
+    });
+}
+
+fn saved_window_size(settings: &WhisperSettings) -> WindowSize {
+    WindowSize {
+        width: settings.screen_width.max(0) as u32,
+        height: settings.screen_height.max(0) as u32,
+    }
+}
+
+// False if that's already the saved size
+fn record_window_size(settings: &mut WhisperSettings, size: WindowSize) -> bool {
+    if saved_window_size(settings) == size {
+        return false;
+    }
+    settings.screen_width = i32::try_from(size.width).unwrap_or(i32::MAX);
+    settings.screen_height = i32::try_from(size.height).unwrap_or(i32::MAX);
+    true
+}
+
+const MIN_WINDOW_SIZE: WindowSize = WindowSize {
+    width: 200,
+    height: 150,
+};
+
+fn apply_window_size(window: &Window, requested: WindowSize) -> Result<WindowSize, String> {
+    let scale = pixel_scale(window);
+    let requested = scale_size(requested, scale);
+    let size = clamp_window_size(requested, max_window_size(window)?);
+    if size != requested {
+        warn!(
+            "⚠️  Window size {}x{} clamped to {}x{}",
+            requested.width, requested.height, size.width, size.height
+        );
+    }
+
+    window
+        .set_size(tauri::Size::Physical(tauri::PhysicalSize {
+            width: size.width,
+            height: size.height,
+        }))
+        .map_err(|e| format!("Failed to set window size: {}", e))?;
+
+    Ok(scale_size(size, 1.0 / scale))
+}
+
+// The monitor the window is on, or the largest one if that's unknown
+fn max_window_size(window: &Window) -> Result<Option<WindowSize>, String> {
+    let current = window
+        .current_monitor()
+        .map_err(|e| format!("Failed to get current monitor: {}", e))?;
+    let monitors = match current {
+        Some(monitor) => vec![monitor],
+        None => window
+            .available_monitors()
+            .map_err(|e| format!("Failed to list monitors: {}", e))?,
+    };
+
+    Ok(monitors
+        .iter()
+        .map(|monitor| WindowSize {
+            width: monitor.size().width,
+            height: monitor.size().height,
+        })
+        .max_by_key(|size| size.width as u64 * size.height as u64))
+}
+
+// The minimum wins on a monitor smaller than it
+fn clamp_window_size(size: WindowSize, max: Option<WindowSize>) -> WindowSize {
+    let max = max.unwrap_or(WindowSize {
+        width: u32::MAX,
+        height: u32::MAX,
+    });
+    WindowSize {
+        width: size.width.min(max.width).max(MIN_WINDOW_SIZE.width),
+        height: size.height.min(max.height).max(MIN_WINDOW_SIZE.height),
+    }
+}
+
+// ============================================================================
+// Logical Pixels
+// ============================================================================
+// Physical pixels per unit: the monitor's scale factor with the logical_pixels
+// setting on (the default), 1 with it off
+fn pixel_scale(window: &Window) -> f64 {
+    let state = window.state::<Mutex<Option<WhisperSettings>>>();
+    let logical = match state.lock().as_deref() {
+        Ok(Some(settings)) => settings.logical_pixels,
+        _ => WhisperSettings::default().logical_pixels,
+    };
+    if !logical {
+        return 1.0;
+    }
+    window.scale_factor().unwrap_or(1.0)
+}
+
+fn to_physical(value: i32, scale: f64) -> i32 {
+    (value as f64 * scale).round() as i32
+}
+
+fn scale_size(size: WindowSize, scale: f64) -> WindowSize {
+    WindowSize {
+        width: (size.width as f64 * scale).round() as u32,
+        height: (size.height as f64 * scale).round() as u32,
+    }
+}
+
+// ============================================================================
+// Set Window Title
+// ============================================================================
+#[tauri::command]
+pub fn set_window_title_command(window: Window, title: String) -> Result<(), String> {
+    window
+        .set_title(&title)
+        .map_err(|e| format!("Failed to set window title: {}", e))?;
+    Ok(())
+}
+
+// ============================================================================
+// Set Window Focusable
+// ============================================================================
+// A non-focusable overlay never takes keyboard focus, so typing stays in the
+// app underneath while it's visible. Saved as the focusable setting.
+#[tauri::command]
+pub fn set_window_focusable_command(window: Window, focusable: bool) -> Result<(), String> {
+    set_focusable(&window, focusable)?;
+
+    let app_handle = window.app_handle();
+    update_settings(&app_handle, &app_handle.state(), |settings| {
+        settings.focusable = focusable
+    })?;
+    Ok(())
+}
+
+// Native code: Tauri v1 has no set_focusable(). WS_EX_NOACTIVATE is one of
+// the ExStyles, so showing the window doesn't clear it.
+#[cfg(target_os = "windows")]
+pub fn set_focusable(window: &Window, focusable: bool) -> Result<(), String> {
+    use windows_sys::Win32::UI::WindowsAndMessaging::WS_EX_NOACTIVATE;
+
+    let hwnd = window
+        .hwnd()
+        .map_err(|e| format!("Failed to get window handle: {}", e))?
+        .0;
+
+    window
+        .state::<ExStyles>()
+        .set(hwnd, WS_EX_NOACTIVATE, !focusable);
+    info!("🎯 Window focusable: {}", focusable);
+    Ok(())
+}
+
+// AppKit asks the window itself (canBecomeKeyWindow), so the NSWindow is
+// switched to a subclass that says no, and back to restore it
+#[cfg(target_os = "macos")]
+pub fn set_focusable(window: &Window, focusable: bool) -> Result<(), String> {
+    use cocoa::base::id;
+    use objc::declare::ClassDecl;
+    use objc::runtime::{Class, Object, Sel, BOOL, NO};
+    use objc::{msg_send, sel, sel_impl};
+
+    extern "C" {
+        fn object_setClass(object: id, class: *const Class) -> *const Class;
+    }
+    extern "C" fn refuse(_: &Object, _: Sel) -> BOOL {
+        NO
+    }
+    const NON_FOCUSABLE_CLASS: &str = "WhisprGPTNonFocusableWindow";
+
+    let ns_window = window
+        .ns_window()
+        .map_err(|e| format!("Failed to get native window: {}", e))? as usize;
+
+    window
+        .run_on_main_thread(move || unsafe {
+            let ns_window = ns_window as id;
+            let current = (*ns_window).class();
+            let subclassed = current.name() == NON_FOCUSABLE_CLASS;
+
+            if focusable {
+                if let (true, Some(original)) = (subclassed, current.superclass()) {
+                    object_setClass(ns_window, original);
+                }
+            } else if !subclassed {
+                let class = Class::get(NON_FOCUSABLE_CLASS).unwrap_or_else(|| {
+                    let refuse = refuse as extern "C" fn(&Object, Sel) -> BOOL;
+                    let mut decl = ClassDecl::new(NON_FOCUSABLE_CLASS, current).unwrap();
+                    decl.add_method(sel!(canBecomeKeyWindow), refuse);
+                    decl.add_method(sel!(canBecomeMainWindow), refuse);
+                    decl.register()
+                });
+                object_setClass(ns_window, class);
+                let _: () = msg_send![ns_window, resignKeyWindow];
+            }
+        })
+        .map_err(|e| format!("Failed to set window focusable: {}", e))?;
+
+    info!("🎯 Window focusable: {}", focusable);
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub fn set_focusable(_window: &Window, _focusable: bool) -> Result<(), String> {
+    Err("Making the window non-focusable is not supported on Linux.".to_string())
+}
+
+// ============================================================================
+// Content Protection
+// ============================================================================
+// Hides the window from screen captures and recordings (it shows up black or
+// not at all). Windows 10 2004+ and macOS only.
+pub fn set_content_protection(window: &Window, enabled: bool) -> Result<(), String> {
+    set_native_content_protection(window, enabled)?;
+    if let Some(protection) = window.try_state::<ContentProtection>() {
+        protection.enabled.store(enabled, Ordering::SeqCst);
+    }
+    Ok(())
+}
+
+// Whether content protection was last turned on through set_content_protection
+pub fn content_protection_enabled(window: &Window) -> bool {
+    window
+        .try_state::<ContentProtection>()
+        .is_some_and(|protection| protection.enabled.load(Ordering::SeqCst))
+}
+
+#[cfg(target_os = "windows")]
+fn set_native_content_protection(window: &Window, enabled: bool) -> Result<(), String> {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        SetWindowDisplayAffinity, WDA_EXCLUDEFROMCAPTURE, WDA_NONE,
+    };
+
+    let hwnd = window
+        .hwnd()
+        .map_err(|e| format!("Failed to get window handle: {}", e))?
+        .0;
+    let affinity = if enabled {
+        WDA_EXCLUDEFROMCAPTURE
+    } else {
+        WDA_NONE
+    };
+
+    if unsafe { SetWindowDisplayAffinity(hwnd, affinity) } == 0 {
+        return Err("Failed to set content protection".to_string());
+    }
+    info!(
+        "🛡️  Content protection {}",
+        if enabled { "on" } else { "off" }
+    );
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn set_native_content_protection(window: &Window, enabled: bool) -> Result<(), String> {
+    use cocoa::base::id;
+    use objc::{msg_send, sel, sel_impl};
+
+    let ns_window = window
+        .ns_window()
+        .map_err(|e| format!("Failed to get native window: {}", e))? as usize;
+    // NSWindowSharingNone = 0, NSWindowSharingReadOnly = 1
+    let sharing_type: u64 = if enabled { 0 } else { 1 };
+
+    window
+        .run_on_main_thread(move || unsafe {
+            let _: () = msg_send![ns_window as id, setSharingType: sharing_type];
+        })
+        .map_err(|e| format!("Failed to set content protection: {}", e))?;
+
+    info!(
+        "🛡️  Content protection {}",
+        if enabled { "on" } else { "off" }
+    );
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn set_native_content_protection(_window: &Window, _enabled: bool) -> Result<(), String> {
+    Err("Content protection is not supported on Linux.".to_string())
+}
+
+// ============================================================================
+// Set Pass-through Regions (per-region click-through)
+// ============================================================================
+// Only the given rects (logical px, relative to the window content) receive
+// clicks; everything else passes through to the app underneath. Tauri can only
+// toggle click-through for the whole window, so a background thread polls the
+// cursor and flips set_ignore_cursor_events as it enters or leaves a rect.
+// An empty list stops polling and makes the whole window interactive again.
+#[tauri::command]
+pub fn set_passthrough_regions_command(
+    window: Window,
+    regions: State<PassthroughRegions>,
+    interactive_rects: Vec<Rect>,
+) -> Result<(), String> {
+    if cfg!(target_os = "linux") {
+        return Err("Pass-through regions are not supported on Linux.".to_string());
+    }
+
+    if let Some(rect) = interactive_rects.iter().find(|r| !r.is_valid()) {
+        return Err(format!("Invalid interactive rect: {:?}", rect));
+    }
+
+    let is_empty = interactive_rects.is_empty();
+    *regions.rects.lock().unwrap() = interactive_rects;
+    regions.whole_window.store(false, Ordering::SeqCst);
+
+    // Bumping the generation stops any poller that is already running
+    let generation = regions.generation.fetch_add(1, Ordering::SeqCst) + 1;
+
+    if is_empty {
+        window
+            .set_ignore_cursor_events(false)
+            .map_err(|e| format!("Failed to restore cursor events: {}", e))?;
+        info!("🖱️  Pass-through regions cleared");
+    } else {
+        spawn_passthrough_poller(
+            window,
+            regions.rects.clone(),
+            regions.generation.clone(),
+            generation,
+        );
+        info!("🖱️  Pass-through regions enabled");
+    }
+
+    Ok(())
+}
+
+fn spawn_passthrough_poller(
+    window: Window,
+    rects: Arc<Mutex<Vec<Rect>>>,
+    generation: Arc<AtomicU64>,
+    my_generation: u64,
+) {
+    std::thread::spawn(move || {
+        let mut ignoring: Option<bool> = None;
+
+        while generation.load(Ordering::SeqCst) == my_generation {
+            let (origin, scale) = match (window.inner_position(), window.scale_factor()) {
+                (Ok(origin), Ok(scale)) => (origin, scale),
+                // Window is gone - nothing left to poll
+                _ => break,
+            };
+
+            if let Some((cursor_x, cursor_y)) = cursor_position(&window) {
+                let x = (cursor_x - origin.x) as f64 / scale;
+                let y = (cursor_y - origin.y) as f64 / scale;
+                let inside = rects.lock().unwrap().iter().any(|r| r.contains(x, y));
+
+                let ignore = !inside;
+                if ignoring != Some(ignore) && window.set_ignore_cursor_events(ignore).is_ok() {
+                    ignoring = Some(ignore);
+                }
+            }
+
+            std::thread::sleep(PASSTHROUGH_POLL_INTERVAL);
+        }
+    });
+}
+
+const PASSTHROUGH_POLL_INTERVAL: Duration = Duration::from_millis(30);
+
+// Global cursor position in physical screen pixels
+#[cfg(target_os = "windows")]
+fn cursor_position(_window: &Window) -> Option<(i32, i32)> {
+    use windows_sys::Win32::Foundation::POINT;
+    use windows_sys::Win32::UI::WindowsAndMessaging::GetCursorPos;
+
+    let mut point = POINT { x: 0, y: 0 };
+    if unsafe { GetCursorPos(&mut point) } != 0 {
+        Some((point.x, point.y))
+    } else {
+        None
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn cursor_position(window: &Window) -> Option<(i32, i32)> {
+    use cocoa::appkit::{NSEvent, NSScreen};
+    use cocoa::base::nil;
+    use cocoa::foundation::NSArray;
+
+    // AppKit reports points with a bottom-left origin on the primary screen
+    let scale = window.scale_factor().ok()?;
+    unsafe {
+        let location = NSEvent::mouseLocation(nil);
+        let screens = NSScreen::screens(nil);
+        if screens.count() == 0 {
+            return None;
+        }
+        let primary_height = NSScreen::frame(screens.objectAtIndex(0)).size.height;
+        Some((
+            (location.x * scale) as i32,
+            ((primary_height - location.y) * scale) as i32,
+        ))
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn cursor_position(_window: &Window) -> Option<(i32, i32)> {
+    None
+}
+
+// ============================================================================
+// Ensure Window Visible
+// ============================================================================
+// Moves the window to the center of the primary monitor if it's no longer on any
+// connected monitor (e.g. after undocking). Returns true if it had to be moved.
+#[tauri::command]
+pub fn ensure_window_visible_command(window: Window) -> Result<bool, String> {
+    ensure_window_visible(&window)
+}
+
+pub fn ensure_window_visible(window: &Window) -> Result<bool, String> {
+    let current = window_geometry(window)?;
+
+    let monitors: Vec<WindowGeometry> = window
+        .available_monitors()
+        .map_err(|e| format!("Failed to list monitors: {}", e))?
+        .iter()
+        .map(monitor_geometry)
+        .collect();
+
+    if monitors
+        .iter()
+        .any(|&monitor| is_reachable_on(current, monitor))
+    {
+        return Ok(false);
+    }
+
+    let primary = window
+        .primary_monitor()
+        .map_err(|e| format!("Failed to get primary monitor: {}", e))?
+        .ok_or_else(|| "No primary monitor found".to_string())?;
+    let (x, y) = relocation_target(current, monitor_geometry(&primary));
+
+    window
+        .set_position(tauri::Position::Physical(tauri::PhysicalPosition { x, y }))
+        .map_err(|e| format!("Failed to move window: {}", e))?;
+
+    info!(
+        "🖥️  Window was off-screen at ({}, {}), moved to ({}, {})",
+        current.x, current.y, x, y
+    );
+    let _ = window.emit("window-relocated", WindowGeometry { x, y, ..current });
+    Ok(true)
+}
+
+fn monitor_geometry(monitor: &tauri::Monitor) -> WindowGeometry {
+    WindowGeometry {
+        x: monitor.position().x,
+        y: monitor.position().y,
+        width: monitor.size().width,
+        height: monitor.size().height,
+    }
+}
+
+// Enough of the window must be on the monitor to grab it and drag it back
+const MIN_VISIBLE_PX: i64 = 50;
+
+fn is_reachable_on(window: WindowGeometry, monitor: WindowGeometry) -> bool {
+    let overlap = |start: i32, len: u32, other_start: i32, other_len: u32| {
+        let end = (start as i64 + len as i64).min(other_start as i64 + other_len as i64);
+        end - (start as i64).max(other_start as i64)
+    };
+
+    overlap(window.x, window.width, monitor.x, monitor.width) >= MIN_VISIBLE_PX
+        && overlap(window.y, window.height, monitor.y, monitor.height) >= MIN_VISIBLE_PX
+}
+
+// Centered on the monitor
+fn relocation_target(window: WindowGeometry, monitor: WindowGeometry) -> (i32, i32) {
+    let x = monitor.x + (monitor.width.saturating_sub(window.width) / 2) as i32;
+    let y = monitor.y + (monitor.height.saturating_sub(window.height) / 2) as i32;
+    (x, y)
+}
+
+fn window_geometry(window: &Window) -> Result<WindowGeometry, String> {
+    let position = window
+        .outer_position()
+        .map_err(|e| format!("Failed to get window position: {}", e))?;
+    let size = window
+        .outer_size()
+        .map_err(|e| format!("Failed to get window size: {}", e))?;
+    Ok(WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    })
+}
+
+// ============================================================================
+// Window Position
+// ============================================================================
+// Remembers where the window is, so the next launch opens it there
+#[tauri::command]
+pub fn save_window_position_command(
+    app_handle: AppHandle,
+    window: Window,
+    state: State<Mutex<Option<WhisperSettings>>>,
+) -> Result<(), String> {
+    let position = window
+        .outer_position()
+        .map_err(|e| format!("Failed to get window position: {}", e))?;
+
+    update_settings(&app_handle, &state, |settings| {
+        settings.window_x = Some(position.x);
+        settings.window_y = Some(position.y);
+    })?;
+
+    info!("📍 Window position saved: ({}, {})", position.x, position.y);
+    Ok(())
+}
+
+// Startup: moves the window to the saved position, pulled fully onto the
+// monitor it overlaps most. A position on no monitor at all is left to
+// ensure_window_visible. Returns false if no position was saved.
+pub fn restore_window_position(
+    window: &Window,
+    settings: &WhisperSettings,
+) -> Result<bool, String> {
+    let (Some(x), Some(y)) = (settings.window_x, settings.window_y) else {
+        return Ok(false);
+    };
+
+    let saved = WindowGeometry {
+        x,
+        y,
+        ..window_geometry(window)?
+    };
+    let monitors: Vec<WindowGeometry> = window
+        .available_monitors()
+        .map_err(|e| format!("Failed to list monitors: {}", e))?
+        .iter()
+        .map(monitor_geometry)
+        .collect();
+    let (x, y) = match monitors
+        .iter()
+        .max_by_key(|&&monitor| overlap_area(saved, monitor))
+        .filter(|&&monitor| overlap_area(saved, monitor) > 0)
+    {
+        Some(&monitor) => clamp_into(saved, monitor),
+        None => (x, y),
+    };
+
+    window
+        .set_position(tauri::Position::Physical(tauri::PhysicalPosition { x, y }))
+        .map_err(|e| format!("Failed to move window: {}", e))?;
+
+    info!("📍 Window position restored: ({}, {})", x, y);
+    Ok(true)
+}
+
+fn overlap_area(a: WindowGeometry, b: WindowGeometry) -> i64 {
+    let overlap = |start: i32, len: u32, other_start: i32, other_len: u32| {
+        let end = (start as i64 + len as i64).min(other_start as i64 + other_len as i64);
+        (end - (start as i64).max(other_start as i64)).max(0)
+    };
+    overlap(a.x, a.width, b.x, b.width) * overlap(a.y, a.height, b.y, b.height)
+}
+
+// Top-left corner that puts all of `window` inside `monitor` (or as much as
+// fits, starting from the monitor's top-left)
+fn clamp_into(window: WindowGeometry, monitor: WindowGeometry) -> (i32, i32) {
+    let clamp = |start: i32, len: u32, min: i32, available: u32| {
+        let max = min as i64 + available.saturating_sub(len) as i64;
+        (start as i64).clamp(min as i64, max) as i32
+    };
+    (
+        clamp(window.x, window.width, monitor.x, monitor.width),
+        clamp(window.y, window.height, monitor.y, monitor.height),
+    )
+}
+
+// ============================================================================
+// Window Slots
+// ============================================================================
+// Saved spots ("reading position", "reference position") to jump between.
+// Slots are numbered 1..=MAX_WINDOW_SLOTS and kept in settings.window_slots.
+#[tauri::command]
+pub fn save_window_slot_command(
+    app_handle: AppHandle,
+    window: Window,
+    state: State<Mutex<Option<WhisperSettings>>>,
+    slot: u8,
+) -> Result<WindowGeometry, String> {
+    check_window_slot(slot)?;
+    let geometry = window_geometry(&window)?;
+
+    update_settings(&app_handle, &state, |settings| {
+        settings.window_slots.insert(slot, geometry);
+    })?;
+
+    info!("📌 Saved window slot {}", slot);
+    Ok(geometry)
+}
+
+// Returns false (and leaves the window where it is) if the slot is empty
+#[tauri::command]
+pub fn recall_window_slot_command(
+    app_handle: AppHandle,
+    window: Window,
+    state: State<Mutex<Option<WhisperSettings>>>,
+    slot: u8,
+) -> Result<bool, String> {
+    check_window_slot(slot)?;
+
+    let Some(geometry) = saved_window_slots(&app_handle, &state)?.remove(&slot) else {
+        info!("📌 Window slot {} is empty", slot);
+        return Ok(false);
+    };
+
+    apply_window_geometry(&window, geometry)?;
+    info!("📌 Recalled window slot {}", slot);
+    Ok(true)
+}
+
+// Moves to the saved slot after the one the window is in (the first one if
+// it's somewhere else). Returns the slot, or None if nothing is saved.
+#[tauri::command]
+pub fn cycle_window_slot_command(
+    app_handle: AppHandle,
+    window: Window,
+    state: State<Mutex<Option<WhisperSettings>>>,
+) -> Result<Option<u8>, String> {
+    let slots = saved_window_slots(&app_handle, &state)?;
+    let Some((slot, geometry)) = next_window_slot(&slots, window_geometry(&window)?) else {
+        return Ok(None);
+    };
+
+    apply_window_geometry(&window, geometry)?;
+    info!("📌 Cycled to window slot {}", slot);
+    Ok(Some(slot))
+}
+
+pub const MAX_WINDOW_SLOTS: u8 = 9;
+
+fn check_window_slot(slot: u8) -> Result<(), String> {
+    if !(1..=MAX_WINDOW_SLOTS).contains(&slot) {
+        return Err(format!(
+            "Window slot must be between 1 and {}",
+            MAX_WINDOW_SLOTS
+        ));
+    }
+    Ok(())
+}
+
+fn saved_window_slots(
+    app_handle: &AppHandle,
+    state: &Mutex<Option<WhisperSettings>>,
+) -> Result<HashMap<u8, WindowGeometry>, String> {
+    match state.lock().unwrap().as_ref() {
+        Some(settings) => Ok(settings.window_slots.clone()),
+        None => Ok(load_settings(app_handle)?.window_slots),
+    }
+}
+
+fn next_window_slot(
+    slots: &HashMap<u8, WindowGeometry>,
+    current: WindowGeometry,
+) -> Option<(u8, WindowGeometry)> {
+    let mut saved: Vec<(u8, WindowGeometry)> = slots.iter().map(|(&s, &g)| (s, g)).collect();
+    saved.sort_by_key(|&(slot, _)| slot);
+
+    let next = match saved.iter().position(|&(_, geometry)| geometry == current) {
+        Some(index) => (index + 1) % saved.len(),
+        None => 0,
+    };
+    saved.get(next).copied()
+}
+
+// A slot saved on a monitor that's gone since is pulled back on screen
+fn apply_window_geometry(window: &Window, geometry: WindowGeometry) -> Result<(), String> {
+    window
+        .set_size(tauri::Size::Physical(tauri::PhysicalSize {
+            width: geometry.width,
+            height: geometry.height,
+        }))
+        .map_err(|e| format!("Failed to set window size: {}", e))?;
+    window
+        .set_position(tauri::Position::Physical(tauri::PhysicalPosition {
+            x: geometry.x,
+            y: geometry.y,
+        }))
+        .map_err(|e| format!("Failed to move window: {}", e))?;
+    ensure_window_visible(window)?;
+    Ok(())
+}
+
+// ============================================================================
+// Display Change Watcher
+// ============================================================================
+// Re-runs ensure_window_visible whenever the display configuration changes.
+// The window handed to the native callback is leaked on purpose - it lives as
+// long as the app.
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn on_display_change(window: &Window) {
+    let window = window.clone();
+
+    // Window getters go through the event loop, so keep them out of the
+    // native callback; also give the OS a moment to settle the new layout
+    std::thread::spawn(move || {
+        std::thread::sleep(DISPLAY_SETTLE_DELAY);
+        if let Err(e) = ensure_window_visible(&window) {
+            log::error!("❌ Failed to check window after display change: {}", e);
+        }
+    });
+}
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+const DISPLAY_SETTLE_DELAY: Duration = Duration::from_millis(500);
+
+// WM_DISPLAYCHANGE, via a subclass of the window procedure. Subclassing only
+// works from the thread that owns the window, so call this from setup(). The
+// same subclass keeps the window's ExStyles.
+#[cfg(target_os = "windows")]
+pub fn watch_display_changes(window: &Window) -> Result<(), String> {
+    use windows_sys::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+    use windows_sys::Win32::UI::Shell::{DefSubclassProc, SetWindowSubclass};
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        WM_DISPLAYCHANGE, WM_STYLECHANGED, WM_STYLECHANGING,
+    };
+
+    unsafe extern "system" fn subclass_proc(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+        _id: usize,
+        data: usize,
+    ) -> LRESULT {
+        let window = &*(data as *const Window);
+        if msg == WM_DISPLAYCHANGE {
+            on_display_change(window);
+        }
+        if msg == WM_STYLECHANGING || msg == WM_STYLECHANGED {
+            if let Some(styles) = window.try_state::<ExStyles>() {
+                styles.on_style_change(hwnd, msg, wparam, lparam);
+            }
+        }
+        DefSubclassProc(hwnd, msg, wparam, lparam)
+    }
+
+    let hwnd = window
+        .hwnd()
+        .map_err(|e| format!("Failed to get window handle: {}", e))?
+        .0;
+    let data = Box::into_raw(Box::new(window.clone()));
+
+    if unsafe { SetWindowSubclass(hwnd, Some(subclass_proc), 1, data as usize) } == 0 {
+        drop(unsafe { Box::from_raw(data) });
+        return Err("Failed to subclass window".to_string());
+    }
+
+    info!("🖥️  Watching for display changes");
+    Ok(())
+}
+
+// NSApplicationDidChangeScreenParametersNotification, via a small observer class
+#[cfg(target_os = "macos")]
+pub fn watch_display_changes(window: &Window) -> Result<(), String> {
+    use cocoa::base::{id, nil};
+    use cocoa::foundation::NSString;
+    use objc::declare::ClassDecl;
+    use objc::runtime::{Class, Object, Sel};
+    use objc::{class, msg_send, sel, sel_impl};
+
+    extern "C" fn screens_changed(this: &Object, _sel: Sel, _notification: id) {
+        unsafe {
+            let data: usize = *this.get_ivar("window");
+            on_display_change(&*(data as *const Window));
+        }
+    }
+
+    let data = Box::into_raw(Box::new(window.clone())) as usize;
+
+    window
+        .run_on_main_thread(move || unsafe {
+            let observer_class = Class::get("WhisprDisplayObserver").unwrap_or_else(|| {
+                let mut decl = ClassDecl::new("WhisprDisplayObserver", class!(NSObject)).unwrap();
+                decl.add_ivar::<usize>("window");
+                decl.add_method(
+                    sel!(screensChanged:),
+                    screens_changed as extern "C" fn(&Object, Sel, id),
+                );
+                decl.register()
+            });
+
+            let observer: id = msg_send![observer_class, new];
+            (*observer).set_ivar::<usize>("window", data);
+
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let name =
+                NSString::alloc(nil).init_str("NSApplicationDidChangeScreenParametersNotification");
+            let _: () = msg_send![center, addObserver: observer
+                                           selector: sel!(screensChanged:)
+                                               name: name
+                                             object: nil];
+        })
+        .map_err(|e| format!("Failed to watch display changes: {}", e))?;
+
+    info!("🖥️  Watching for display changes");
+    Ok(())
+}
+
+// No display-change notification through Tauri v1 here;
+// ensure_window_visible_command can still be called manually
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub fn watch_display_changes(_window: &Window) -> Result<(), String> {
+    Ok(())
+}
+
+// ============================================================================
+// Get Feature Support
+// ============================================================================
+// Lets the UI hide or disable controls the current build can't honor instead
+// of letting users hit the errors above. Keep this in sync as features get
+// implemented natively.
+#[tauri::command]
+pub fn get_feature_support_command(app_handle: AppHandle) -> Result<FeatureSupport, String> {
+    let is_desktop_native = cfg!(any(target_os = "windows", target_os = "macos"));
+    let global_shortcuts = global_shortcuts_supported(&app_handle)?;
+
+    Ok(FeatureSupport {
+        platform: std::env::consts::OS.to_string(),
+        tauri_version: tauri::VERSION.to_string(),
+        opacity: is_desktop_native,
+        focusable: is_desktop_native,
+        click_through: true,
+        passthrough_regions: is_desktop_native,
+        content_protection: is_desktop_native,
+        blur: false,
+        always_on_top: true,
+        global_shortcuts,
+    })
+}
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BuildInfo {
+    pub version: String,
+    pub commit: String,
+    pub build_timestamp: String,
+    pub target: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FeatureSupport {
+    pub platform: String,
+    pub tauri_version: String,
+    pub opacity: bool,
+    pub focusable: bool,
+    pub click_through: bool,
+    pub passthrough_regions: bool,
+    pub content_protection: bool,
+    pub blur: bool,
+    pub always_on_top: bool,
+    pub global_shortcuts: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct WindowSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    fn is_valid(&self) -> bool {
+        [self.x, self.y, self.width, self.height]
+            .iter()
+            .all(|v| v.is_finite())
+            && self.width >= 0.0
+            && self.height >= 0.0
+    }
+
+    fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+}
+
+// Ordered from lowest to highest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum WindowLevel {
+    Normal,
+    #[default]
+    Floating,
+    Top,
+    ScreenSaver,
+}
+
+impl WindowLevel {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "normal" => Ok(Self::Normal),
+            "floating" => Ok(Self::Floating),
+            "top" => Ok(Self::Top),
+            "screensaver" => Ok(Self::ScreenSaver),
+            _ => Err(format!(
+                "Unknown window level '{}' (expected normal, floating, top or screensaver)",
+                name
+            )),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Floating => "floating",
+            Self::Top => "top",
+            Self::ScreenSaver => "screensaver",
+        }
+    }
+
+    fn is_topmost(self) -> bool {
+        self != Self::Normal
+    }
+
+    // NSNormalWindowLevel, NSFloatingWindowLevel, NSStatusWindowLevel, NSScreenSaverWindowLevel
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    fn ns_window_level(self) -> i64 {
+        match self {
+            Self::Normal => 0,
+            Self::Floating => 3,
+            Self::Top => 25,
+            Self::ScreenSaver => 1000,
+        }
+    }
+}
+
+// Managed state: the level last applied with apply_window_level, and the
+// pending restore of flash_to_front_command (flash id + its cancel sender)
+#[derive(Default)]
+pub struct WindowLevelState {
+    current: Mutex<WindowLevel>,
+    flash: Mutex<Option<(u64, Sender<()>)>>,
+    next_flash_id: AtomicU64,
+}
+
+// Managed state: stops the always-on-top watcher when dropped or replaced
+#[derive(Default)]
+pub struct TopmostWatcher {
+    stop: Mutex<Option<Sender<()>>>,
+}
+
+// Managed state for the pass-through poller, plus whether whole-window
+// click-through is on (set_ignore_cursor_events_command)
+#[derive(Default)]
+pub struct PassthroughRegions {
+    rects: Arc<Mutex<Vec<Rect>>>,
+    generation: Arc<AtomicU64>,
+    whole_window: AtomicBool,
+}
+
+impl PassthroughRegions {
+    // While regions are set, the poller owns click-through
+    pub fn is_active(&self) -> bool {
+        !self.rects.lock().unwrap().is_empty()
+    }
+
+    pub fn whole_window(&self) -> bool {
+        self.whole_window.load(Ordering::SeqCst)
+    }
+}
+
+// Managed state: the fade in progress (bumping `generation` stops it) and
+// the native opacity levels, which fades only change while holding `levels`
+#[derive(Default)]
+pub struct WindowFade {
+    generation: AtomicU64,
+    levels: Mutex<FadeLevels>,
+}
+
+// None until something sets an opacity (windows start opaque)
+#[derive(Default)]
+struct FadeLevels {
+    current: Option<f64>,
+    // What the window is shown at once any fade in finishes
+    shown: Option<f64>,
+}
+
+// Managed state: whether content protection is currently on
+#[derive(Default)]
+pub struct ContentProtection {
+    enabled: AtomicBool,
+}
+
+// Managed state: the extended styles the app keeps on the main window across
+// tao's rewrites (Windows only, see Extended Window Styles)
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub struct ExStyles {
+    kept: AtomicU32,
+    // The layered window alpha last set
+    alpha: AtomicU8,
+}
+
+impl Default for ExStyles {
+    fn default() -> Self {
+        Self {
+            kept: AtomicU32::new(0),
+            alpha: AtomicU8::new(u8::MAX),
+        }
+    }
+}
+
+// ============================================================================
+// TAURI V1 vs V2 NOTES:
+// ============================================================================
+// Missing in v1 (available in v2):
+// - set_opacity() - Window transparency (native code on Windows/macOS)
+// - set_focusable() - Whether window can be focused (native code on Windows/macOS)
+//
+// Workarounds:
+// 1. Use CSS opacity instead of window opacity (Linux)
 // 2. Upgrade to Tauri v2 for full API support
 // 3. Use platform-specific native code (complex)
 //
@@ -207,5 +2242,383 @@ pub fn set_window_focusable_command(_window: Window, _focusable: bool) -> Result
 // ✅ set_always_on_top
 // ✅ set_position
 // ✅ set_size
+// ✅ set_ignore_cursor_events (pass-through clicks)
 // ✅ close
 // ✅ restart (app level)
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_levels_round_trip_through_their_names() {
+        for name in ["normal", "floating", "top", "screensaver"] {
+            assert_eq!(WindowLevel::parse(name).unwrap().as_str(), name);
+        }
+        assert!(WindowLevel::parse("above-everything").is_err());
+    }
+
+    #[test]
+    fn always_on_top_off_means_the_normal_level() {
+        let mut settings = WhisperSettings {
+            window_level: "top".to_string(),
+            ..WhisperSettings::default()
+        };
+        assert_eq!(window_level_for(&settings), WindowLevel::Top);
+
+        settings.always_on_top = false;
+        assert_eq!(window_level_for(&settings), WindowLevel::Normal);
+
+        settings.always_on_top = true;
+        settings.window_level = "unknown".to_string();
+        assert_eq!(window_level_for(&settings), WindowLevel::Floating);
+    }
+
+    #[test]
+    fn window_levels_map_to_platform_levels() {
+        assert!(!WindowLevel::Normal.is_topmost());
+        assert!(WindowLevel::Floating.is_topmost());
+        assert!(WindowLevel::ScreenSaver.is_topmost());
+
+        assert_eq!(WindowLevel::Normal.ns_window_level(), 0);
+        assert_eq!(WindowLevel::Floating.ns_window_level(), 3);
+        assert_eq!(WindowLevel::Top.ns_window_level(), 25);
+        assert_eq!(WindowLevel::ScreenSaver.ns_window_level(), 1000);
+    }
+
+    #[test]
+    fn window_slots_round_trip_and_cycle() {
+        let reading = WindowGeometry {
+            x: 100,
+            y: 50,
+            width: 800,
+            height: 600,
+        };
+        let reference = WindowGeometry {
+            x: -1200,
+            y: 300,
+            width: 400,
+            height: 900,
+        };
+
+        let mut settings = WhisperSettings::default();
+        settings.window_slots.insert(1, reading);
+        settings.window_slots.insert(3, reference);
+        let json = serde_json::to_string(&settings).unwrap();
+        let saved: WhisperSettings = serde_json::from_str(&json).unwrap();
+        assert_eq!(saved.window_slots, settings.window_slots);
+
+        let slots = saved.window_slots;
+        assert_eq!(next_window_slot(&slots, reading), Some((3, reference)));
+        assert_eq!(next_window_slot(&slots, reference), Some((1, reading)));
+        let elsewhere = WindowGeometry { x: 0, ..reading };
+        assert_eq!(next_window_slot(&slots, elsewhere), Some((1, reading)));
+        assert_eq!(next_window_slot(&HashMap::new(), reading), None);
+
+        assert!(check_window_slot(1).is_ok());
+        assert!(check_window_slot(MAX_WINDOW_SLOTS).is_ok());
+        assert!(check_window_slot(0).is_err());
+        assert!(check_window_slot(MAX_WINDOW_SLOTS + 1).is_err());
+    }
+
+    #[test]
+    fn restored_positions_are_clamped_onto_the_monitor() {
+        let monitor = WindowGeometry {
+            x: 1920,
+            y: 0,
+            width: 1280,
+            height: 1024,
+        };
+        let window = |x, y| WindowGeometry {
+            x,
+            y,
+            width: 500,
+            height: 400,
+        };
+
+        // Fully on screen: unchanged
+        assert_eq!(clamp_into(window(2000, 100), monitor), (2000, 100));
+        // Hanging off the right and bottom edges
+        assert_eq!(clamp_into(window(3000, 900), monitor), (2700, 624));
+        // Hanging off the left (onto the primary monitor) and the top
+        assert_eq!(clamp_into(window(1800, -50), monitor), (1920, 0));
+
+        assert_eq!(overlap_area(window(3100, 0), monitor), 100 * 400);
+        assert_eq!(overlap_area(window(0, 0), monitor), 0);
+    }
+
+    #[test]
+    fn off_screen_windows_move_to_the_primary_center() {
+        let primary = WindowGeometry {
+            x: 0,
+            y: 0,
+            width: 1920,
+            height: 1080,
+        };
+        let window = |x, y| WindowGeometry {
+            x,
+            y,
+            width: 600,
+            height: 400,
+        };
+
+        assert!(is_reachable_on(window(100, 100), primary));
+        // Only a sliver left on screen still counts as lost
+        assert!(!is_reachable_on(window(1900, 100), primary));
+        // Saved on a monitor that's been unplugged
+        assert!(!is_reachable_on(window(2500, 300), primary));
+
+        assert_eq!(relocation_target(window(2500, 300), primary), (660, 340));
+    }
+
+    #[test]
+    fn force_quit_only_targets_descendants() {
+        let processes = [
+            (1, None),
+            (100, Some(1)),   // the app
+            (101, Some(100)), // webview
+            (102, Some(101)), // webview helper
+            (200, Some(1)),   // unrelated
+            (201, Some(200)),
+        ];
+        assert_eq!(descendant_pids(&processes, 100), vec![101, 102]);
+        assert!(descendant_pids(&processes, 102).is_empty());
+    }
+
+    #[test]
+    fn resized_windows_reopen_at_the_saved_size() {
+        let dir = std::env::temp_dir().join(format!("whisprgpt-size-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(crate::state::settings::SETTINGS_FILE);
+
+        let mut settings = WhisperSettings::default();
+        let resized = WindowSize {
+            width: 640,
+            height: 480,
+        };
+        assert!(record_window_size(&mut settings, resized));
+        assert!(!record_window_size(&mut settings, resized));
+
+        let json = serde_json::to_string(&settings).unwrap();
+        crate::state::settings::write_atomic(&path, &json).unwrap();
+        let loaded = crate::state::settings::load_settings_from(&path).unwrap();
+        assert_eq!(saved_window_size(&loaded), resized);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn window_sizes_are_clamped_to_the_monitor() {
+        let size = |width, height| WindowSize { width, height };
+        let clamp = |width, height, max| clamp_window_size(size(width, height), max);
+        let monitor = Some(size(1920, 1080));
+
+        assert_eq!(clamp(0, 0, monitor), MIN_WINDOW_SIZE);
+        assert_eq!(clamp(10_000, 50, monitor), size(1920, 150));
+        assert_eq!(clamp(500, 400, monitor), size(500, 400));
+        assert_eq!(clamp(10_000, 10_000, None), size(10_000, 10_000));
+
+        // A monitor smaller than the minimum
+        assert_eq!(clamp(500, 400, Some(size(100, 100))), MIN_WINDOW_SIZE);
+    }
+
+    #[test]
+    fn logical_pixels_scale_with_the_monitor() {
+        // A 2.0 scale factor doubles every delta and size
+        assert_eq!(to_physical(20, 2.0), 40);
+        assert_eq!(to_physical(-20, 2.0), -40);
+        assert_eq!(to_physical(20, 1.25), 25);
+        assert_eq!(to_physical(20, 1.0), 20);
+
+        let size = WindowSize {
+            width: 500,
+            height: 400,
+        };
+        let physical = scale_size(size, 2.0);
+        assert_eq!((physical.width, physical.height), (1000, 800));
+        assert_eq!(scale_size(physical, 1.0 / 2.0), size);
+    }
+
+    #[test]
+    fn opacity_steps_are_clamped_and_rounded() {
+        assert_eq!(stepped_opacity(0.2, 0.1), 0.3);
+        assert_eq!(stepped_opacity(0.95, 0.1), 1.0);
+        assert_eq!(stepped_opacity(0.15, -0.1), MIN_OPACITY);
+        assert_eq!(stepped_opacity(1.0, -0.25), 0.75);
+    }
+
+    #[test]
+    fn fades_ramp_in_frames() {
+        assert_eq!(fade_frames(Duration::from_millis(150)), 10);
+        // Shorter than a frame still gets one, ending at the target
+        assert_eq!(fade_frames(Duration::from_millis(5)), 1);
+        assert_eq!(fade_opacity(0.0, 0.8, 1, 1), 0.8);
+
+        let ramp: Vec<f64> = (1..=4)
+            .map(|frame| fade_opacity(1.0, 0.0, frame, 4))
+            .collect();
+        assert_eq!(ramp, vec![0.75, 0.5, 0.25, 0.0]);
+    }
+
+    #[test]
+    fn next_monitor_keeps_the_relative_spot_and_wraps() {
+        let monitor = |x, width, height| WindowGeometry {
+            x,
+            y: 0,
+            width,
+            height,
+        };
+        let left = monitor(0, 1920, 1080);
+        let right = monitor(1920, 2560, 1440);
+        let window = WindowGeometry {
+            x: 960,
+            y: 108,
+            width: 400,
+            height: 300,
+        };
+
+        assert_eq!(next_monitor_position(window, vec![left]), None);
+        // Listed out of order, still left to right
+        assert_eq!(
+            next_monitor_position(window, vec![right, left]),
+            Some((1920 + 1280, 144))
+        );
+
+        // From the right edge of the last monitor back to the first, pulled on
+        let window = WindowGeometry {
+            x: 1920 + 2400,
+            ..window
+        };
+        assert_eq!(
+            next_monitor_position(window, vec![left, right]),
+            Some((1920 - 400, 81))
+        );
+    }
+
+    #[test]
+    fn moves_snap_to_monitor_edges() {
+        let monitors = [
+            WindowGeometry {
+                x: 0,
+                y: 0,
+                width: 1920,
+                height: 1080,
+            },
+            WindowGeometry {
+                x: 1920,
+                y: 0,
+                width: 1280,
+                height: 1024,
+            },
+        ];
+        let window = |x, y| WindowGeometry {
+            x,
+            y,
+            width: 400,
+            height: 300,
+        };
+
+        // Ending up near an edge, or stepping over it, stops flush with it
+        assert_eq!(snapped_move(window(30, 500), -20, 0, &monitors), (0, 500));
+        assert_eq!(snapped_move(window(10, 500), -20, 0, &monitors), (0, 500));
+        assert_eq!(snapped_move(window(500, 770), 0, 20, &monitors), (500, 780));
+        assert_eq!(
+            snapped_move(window(1505, 500), 20, 0, &monitors),
+            (1520, 500)
+        );
+
+        // Nowhere near an edge
+        assert_eq!(
+            snapped_move(window(500, 500), 20, -20, &monitors),
+            (520, 480)
+        );
+
+        // Already flush: the next move goes past, over to the other monitor
+        assert_eq!(snapped_move(window(0, 500), -20, 0, &monitors), (-20, 500));
+        assert_eq!(
+            snapped_move(window(1520, 500), 20, 0, &monitors),
+            (1540, 500)
+        );
+
+        // Off every monitor: no snapping
+        assert_eq!(snapped_move(window(-900, 0), 20, 0, &monitors), (-880, 0));
+    }
+
+    // A plain window with the main window's style handling, its styles
+    // rewritten around a hide and a show the way tao's apply_diff does
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn ex_styles_survive_hide_and_show() {
+        use windows_sys::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+        use windows_sys::Win32::UI::Shell::{DefSubclassProc, SetWindowSubclass};
+        use windows_sys::Win32::UI::WindowsAndMessaging::{
+            CreateWindowExW, DestroyWindow, GetLayeredWindowAttributes, SetWindowLongW, ShowWindow,
+            GWL_EXSTYLE, SW_HIDE, SW_SHOW, WS_EX_APPWINDOW, WS_EX_LAYERED, WS_EX_NOACTIVATE,
+            WS_POPUP,
+        };
+
+        unsafe extern "system" fn subclass_proc(
+            hwnd: HWND,
+            msg: u32,
+            wparam: WPARAM,
+            lparam: LPARAM,
+            _id: usize,
+            data: usize,
+        ) -> LRESULT {
+            (*(data as *const ExStyles)).on_style_change(hwnd, msg, wparam, lparam);
+            DefSubclassProc(hwnd, msg, wparam, lparam)
+        }
+        fn hide_and_show(hwnd: HWND) {
+            for command in [SW_HIDE, SW_SHOW] {
+                unsafe {
+                    ShowWindow(hwnd, command);
+                    SetWindowLongW(hwnd, GWL_EXSTYLE, WS_EX_APPWINDOW as i32);
+                }
+            }
+        }
+        fn alpha(hwnd: HWND) -> u8 {
+            let (mut key, mut alpha, mut flags) = (0, 0, 0);
+            unsafe { GetLayeredWindowAttributes(hwnd, &mut key, &mut alpha, &mut flags) };
+            alpha
+        }
+
+        let class: Vec<u16> = "STATIC\0".encode_utf16().collect();
+        let hwnd = unsafe {
+            CreateWindowExW(
+                0,
+                class.as_ptr(),
+                std::ptr::null(),
+                WS_POPUP,
+                0,
+                0,
+                100,
+                100,
+                0,
+                0,
+                0,
+                std::ptr::null(),
+            )
+        };
+        assert_ne!(hwnd, 0);
+        let styles = ExStyles::default();
+        let data = &styles as *const ExStyles as usize;
+        assert_ne!(
+            unsafe { SetWindowSubclass(hwnd, Some(subclass_proc), 1, data) },
+            0
+        );
+
+        styles.set_alpha(hwnd, 128).unwrap();
+        styles.set(hwnd, WS_EX_NOACTIVATE, true);
+        hide_and_show(hwnd);
+        let kept = WS_EX_LAYERED | WS_EX_NOACTIVATE;
+        assert_eq!(ex_style(hwnd) & kept, kept);
+        assert_eq!(alpha(hwnd), 128);
+
+        // Turned off, a bit stays off
+        styles.set(hwnd, WS_EX_NOACTIVATE, false);
+        hide_and_show(hwnd);
+        assert_eq!(ex_style(hwnd) & kept, WS_EX_LAYERED);
+
+        unsafe { DestroyWindow(hwnd) };
+    }
+}
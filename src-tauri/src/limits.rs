@@ -0,0 +1,91 @@
+// limits.rs
+// Size limits for data coming in through commands, checked at the command
+// boundary so a buggy (or hostile) frontend or page can't bloat memory, the
+// settings file or the webview with megabytes of input.
+
+use crate::types::WhisperSettings;
+
+// A single prompt: system/retry prompt, a template, text injected into a provider
+pub const MAX_PROMPT_BYTES: usize = 64 * 1024;
+
+// Everything in settings.json, serialized
+pub const MAX_SETTINGS_BYTES: usize = 1024 * 1024;
+
+// Values a provider page reports back (page title, URL)
+pub const MAX_PAGE_REPORT_BYTES: usize = 8 * 1024;
+
+pub fn check_size(what: &str, len: usize, max: usize) -> Result<(), String> {
+    if len > max {
+        return Err(format!(
+            "Input too large: {} is {} bytes (max {})",
+            what, len, max
+        ));
+    }
+    Ok(())
+}
+
+pub fn check_prompt(what: &str, prompt: &str) -> Result<(), String> {
+    check_size(what, prompt.len(), MAX_PROMPT_BYTES)
+}
+
+pub fn check_settings(settings: &WhisperSettings) -> Result<(), String> {
+    check_prompt("System prompt", &settings.system_prompt)?;
+    check_prompt("Retry prompt", &settings.retry_prompt)?;
+    for (name, template) in &settings.prompt_templates {
+        check_prompt(&format!("Template '{}'", name), template)?;
+    }
+
+    let json =
+        serde_json::to_vec(settings).map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    check_size("Settings", json.len(), MAX_SETTINGS_BYTES)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes_up_to_the_limit_are_accepted() {
+        assert!(check_prompt("Prompt", &"a".repeat(MAX_PROMPT_BYTES)).is_ok());
+        assert_eq!(
+            check_prompt("Prompt", &"a".repeat(MAX_PROMPT_BYTES + 1)),
+            Err(format!(
+                "Input too large: Prompt is {} bytes (max {})",
+                MAX_PROMPT_BYTES + 1,
+                MAX_PROMPT_BYTES
+            ))
+        );
+    }
+
+    #[test]
+    fn prompts_are_measured_in_bytes() {
+        // 3 bytes per character in UTF-8
+        let prompt = "€".repeat(MAX_PROMPT_BYTES / 3 + 1);
+        assert!(check_prompt("Prompt", &prompt).is_err());
+    }
+
+    #[test]
+    fn oversized_settings_are_rejected() {
+        let mut settings = WhisperSettings {
+            system_prompt: "a".repeat(MAX_PROMPT_BYTES),
+            ..WhisperSettings::default()
+        };
+        assert!(check_settings(&settings).is_ok());
+
+        settings.retry_prompt = "a".repeat(MAX_PROMPT_BYTES + 1);
+        assert!(check_settings(&settings)
+            .unwrap_err()
+            .starts_with("Input too large: Retry prompt"));
+
+        // Every template fits, but together they go over the total
+        settings.retry_prompt.clear();
+        for i in 0..MAX_SETTINGS_BYTES / MAX_PROMPT_BYTES {
+            settings
+                .prompt_templates
+                .insert(format!("template {}", i), "a".repeat(MAX_PROMPT_BYTES));
+        }
+        assert!(check_settings(&settings)
+            .unwrap_err()
+            .starts_with("Input too large: Settings"));
+    }
+}
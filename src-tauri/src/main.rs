@@ -4,13 +4,21 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod commands;
+mod limits;
+mod single_instance;
 mod state;
 mod types;
 
-use log::{debug, error, info};
+use commands::auth::TokenType;
+use log::{debug, error, info, warn};
+use percent_encoding::percent_decode_str;
+use state::settings_saver::{SettingsSaver, SAVE_DEBOUNCE};
+use state::ui_state::UiState;
 use std::fs::OpenOptions;
 use std::sync::Mutex;
-use tauri::{GlobalShortcutManager, Manager};
+use tauri::{
+    CustomMenuItem, Manager, SystemTray, SystemTrayEvent, SystemTrayMenu, SystemTrayMenuItem,
+};
 use types::WhisperSettings;
 use url::Url;
 
@@ -88,70 +96,349 @@ fn fix_protocol_registration() {
     }
 }
 
+#[cfg(target_os = "linux")]
+fn fix_protocol_registration() {
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    const DESKTOP_FILE: &str = "whisprgpt-handler.desktop";
+    const MIME_TYPE: &str = "x-scheme-handler/whisprgpt";
+
+    // AppImages run from a temporary mount; $APPIMAGE is the file to launch
+    let exe_path = match std::env::var_os("APPIMAGE")
+        .map(PathBuf::from)
+        .map_or_else(std::env::current_exe, Ok)
+    {
+        Ok(path) => path.to_string_lossy().to_string(),
+        Err(e) => {
+            error!("Failed to get current exe path: {}", e);
+            return;
+        }
+    };
+
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")));
+    let applications_dir = match data_home {
+        Some(data_dir) => data_dir.join("applications"),
+        None => {
+            error!("❌ Failed to find the applications directory (no $HOME)");
+            return;
+        }
+    };
+
+    info!("🔧 Checking protocol registration...");
+
+    // Exec quoting per the desktop entry spec: escape inside double quotes,
+    // then escape backslashes again for the file's own string syntax
+    let quoted_exe = exe_path
+        .chars()
+        .fold(String::new(), |mut quoted, c| {
+            if matches!(c, '"' | '`' | '$' | '\\') {
+                quoted.push('\\');
+            }
+            quoted.push(c);
+            quoted
+        })
+        .replace('\\', "\\\\");
+    let desktop_entry = format!(
+        "[Desktop Entry]\nType=Application\nName=WhisprGPT\nExec=\"{}\" %u\nTerminal=false\nNoDisplay=true\nMimeType={};\n",
+        quoted_exe, MIME_TYPE
+    );
+
+    let desktop_path = applications_dir.join(DESKTOP_FILE);
+    if std::fs::read_to_string(&desktop_path).ok().as_deref() == Some(desktop_entry.as_str()) {
+        info!("✅ Protocol already registered correctly");
+        return;
+    }
+
+    info!("🔄 Registering protocol...");
+    if let Err(e) = std::fs::create_dir_all(&applications_dir)
+        .and_then(|_| std::fs::write(&desktop_path, &desktop_entry))
+    {
+        error!("❌ Failed to write {}: {}", desktop_path.display(), e);
+        return;
+    }
+
+    // Refreshing the MIME cache is best effort; xdg-mime is what makes us the handler
+    if let Err(e) = Command::new("update-desktop-database")
+        .arg(&applications_dir)
+        .output()
+    {
+        info!("⚠️  update-desktop-database not available: {}", e);
+    }
+
+    match Command::new("xdg-mime")
+        .args(["default", DESKTOP_FILE, MIME_TYPE])
+        .output()
+    {
+        Ok(output) if output.status.success() => info!("✅ Protocol registered successfully"),
+        Ok(output) => error!(
+            "❌ Failed to register protocol: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+        Err(e) => error!("❌ Failed to run xdg-mime: {}", e),
+    }
+}
+
 fn main() {
-    let log_path = "C:\\ProgramData\\WhisprGPT\\whisprgpt.log";
+    let context = tauri::generate_context!();
+
+    let default_app_dir = tauri::api::path::app_data_dir(context.config());
+
+    // A second launch (e.g. the OS opening an auth link while we're running)
+    // hands its args to the running instance instead of starting another one.
+    // Before the log file is touched: rotating it here would rename the log
+    // the running instance is still writing to.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let handover = default_app_dir
+        .as_deref()
+        .map(|dir| single_instance::forward_to_running_instance(dir, &args));
+    if let Some(Ok(true)) = handover {
+        return;
+    }
 
-    if let Some(parent) = std::path::Path::new(log_path).parent() {
+    // A relocated data dir (relocate_data_dir_command) also takes the log file
+    let app_dir = default_app_dir
+        .as_deref()
+        .map(state::settings::resolve_app_dir);
+    let relocated_dir = app_dir
+        .as_deref()
+        .filter(|dir| Some(*dir) != default_app_dir.as_deref());
+    let log_dir = tauri::api::path::app_log_dir(context.config());
+    let log_path = commands::logs::init_log_file(log_dir.as_deref(), relocated_dir);
+
+    if let Some(parent) = log_path.parent() {
         let _ = std::fs::create_dir_all(parent);
     }
 
+    // Start a fresh file once the log gets big (reported once the logger is up)
+    let rotated = commands::logs::rotate_log_file(
+        &log_path,
+        commands::logs::MAX_LOG_BYTES,
+        commands::logs::LOG_BACKUPS,
+    );
+
+    // settings.log_level, unless RUST_LOG (e.g. "debug" or "whisprgpt=trace") is set
+    let rust_log = std::env::var("RUST_LOG").ok();
+    let log_level = app_dir
+        .as_deref()
+        .map(|dir| commands::logs::configured_log_level(&dir.join(state::settings::SETTINGS_FILE)))
+        .unwrap_or(log::LevelFilter::Info);
+    let logger = || {
+        let mut builder = env_logger::Builder::new();
+        match &rust_log {
+            Some(filters) => builder.parse_filters(filters),
+            // Everything reaches the logger and log::set_max_level filters, so
+            // set_log_level_command can change the level at runtime
+            None => builder.filter_level(log::LevelFilter::Trace),
+        };
+        builder
+    };
+
     // Initialize logger ONCE - either to file or stdout
-    if let Ok(log_file) = OpenOptions::new().create(true).append(true).open(log_path) {
+    if let Ok(log_file) = OpenOptions::new().create(true).append(true).open(&log_path) {
         // Log to file if we can open it
-        env_logger::Builder::from_default_env()
-            .filter_level(log::LevelFilter::Info)
+        logger()
             .target(env_logger::Target::Pipe(Box::new(log_file)))
             .init();
     } else {
         // Fallback to stdout if file fails
-        env_logger::Builder::from_default_env()
-            .filter_level(log::LevelFilter::Info)
-            .init();
+        logger().init();
+    }
+    if rust_log.is_none() {
+        log::set_max_level(log_level);
+    }
+
+    match rotated {
+        Ok(true) => info!("🗂️  Previous log rotated to {}.1", log_path.display()),
+        Ok(false) => {}
+        Err(e) => error!("❌ Failed to rotate log file: {}", e),
+    }
+
+    if let Some(Err(e)) = &handover {
+        info!("🔒 No running instance to hand over to: {}", e);
+    }
+    let instance = default_app_dir.as_deref().and_then(|dir| {
+        single_instance::bind(dir)
+            .map_err(|e| error!("❌ Failed to claim the instance port: {}", e))
+            .ok()
+    });
+
+    // The webview reads its proxy when the main window is created, which
+    // happens before setup() - so apply it from the settings file up front
+    if let Some(app_dir) = &app_dir {
+        let settings_path = app_dir.join(state::settings::SETTINGS_FILE);
+        if let Ok(settings) = state::settings::load_settings_from(&settings_path) {
+            if let Some(proxy_url) = settings.proxy_url {
+                commands::network::apply_webview_proxy(&proxy_url);
+            }
+        }
     }
 
     tauri::Builder::default()
         .manage(Mutex::new(None::<WhisperSettings>))
-        .invoke_handler(tauri::generate_handler![
-            commands::settings::get_settings_command,
-            commands::settings::save_settings_command,
-            commands::settings::reset_settings_command,
-            commands::shortcuts::register_shortcuts_command,
-            commands::shortcuts::unregister_shortcuts_command,
-            commands::shortcuts::update_shortcut_command,
-            commands::shortcuts::reset_shortcut_command,
-            commands::window::close_app_command,
-            commands::window::restart_app_command,
-            commands::window::set_opacity_command,
-            commands::window::move_window_command,
-            commands::window::hide_window_command,
-            commands::window::show_window_command,
-            commands::window::toggle_window_visibility_command,
-            commands::window::set_always_on_top_command,
-            commands::window::get_app_version_command,
-            commands::window::delete_cache_command,
-            commands::window::set_window_size_command,
-            commands::window::set_window_focusable_command,
-            commands::auth::open_external_url,
-            commands::auth::open_checkout_portal,
-            commands::whispr::launch_whispr_mode_command,
-            commands::whispr::navigate_to_dashboard_command,
-            commands::whispr::get_current_route_command,
-        ])
+        .manage(Mutex::new(None::<UiState>))
+        .manage(commands::window::PassthroughRegions::default())
+        .manage(commands::window::WindowLevelState::default())
+        .manage(commands::window::ContentProtection::default())
+        .manage(commands::window::WindowFade::default())
+        .manage(commands::window::ExStyles::default())
+        .manage(commands::window::TopmostWatcher::default())
+        .manage(commands::auth::UrlOpenLimiter::default())
+        .manage(
+            default_app_dir
+                .as_deref()
+                .map(commands::auth::OAuthState::persisted_in)
+                .unwrap_or_default(),
+        )
+        .manage(commands::auth::AuthEventQueue::default())
+        .manage(commands::schedule::ActionScheduler::default())
+        .manage(commands::whispr::ProviderLoadTracker::default())
+        .manage(commands::whispr::NavigationHistory::default())
+        .manage(commands::whispr::NavigationAcks::default())
+        .manage(commands::page::PageTitleRequests::default())
+        .manage(commands::shortcuts::ShortcutSupport::default())
+        .manage(commands::dnd::DoNotDisturb::default())
+        .manage(commands::session::SessionRestore::default())
+        .manage(commands::shortcuts::TextInputFocus::default())
+        .manage(commands::shortcuts::RegisteredShortcuts::default())
+        .manage(commands::shortcuts::ChordSequences::default())
+        .manage(commands::shortcuts::ShortcutRecording::default())
+        .manage(commands::typing::TypingMonitor::default())
+        // A way back to the window that doesn't depend on global shortcuts
+        .system_tray(system_tray())
+        .on_system_tray_event(handle_tray_event)
+        .invoke_handler(commands::page::guard_provider_ipc(
+            tauri::generate_handler![
+                commands::settings::get_settings_command,
+                commands::settings::save_settings_command,
+                commands::settings::reset_settings_command,
+                commands::settings::flush_settings_command,
+                commands::shortcuts::register_shortcuts_command,
+                commands::shortcuts::unregister_shortcuts_command,
+                commands::shortcuts::update_shortcut_command,
+                commands::shortcuts::reset_shortcut_command,
+                commands::shortcuts::export_shortcuts_command,
+                commands::shortcuts::import_shortcuts_command,
+                commands::shortcuts::swap_shortcuts_command,
+                commands::shortcuts::global_shortcuts_supported_command,
+                commands::window::close_app_command,
+                commands::window::restart_app_command,
+                commands::window::force_quit_command,
+                commands::window::set_opacity_command,
+                commands::window::increase_opacity_command,
+                commands::window::decrease_opacity_command,
+                commands::window::move_window_command,
+                commands::window::move_to_next_monitor_command,
+                commands::window::hide_window_command,
+                commands::window::show_window_command,
+                commands::window::panic_hide_command,
+                commands::window::panic_restore_command,
+                commands::window::toggle_window_visibility_command,
+                commands::window::set_always_on_top_command,
+                commands::window::reassert_always_on_top_command,
+                commands::window::toggle_always_on_top_command,
+                commands::window::set_window_level_command,
+                commands::window::flash_to_front_command,
+                commands::dnd::set_dnd_command,
+                commands::window::get_app_version_command,
+                commands::window::get_build_info_command,
+                commands::window::get_system_locale_command,
+                commands::window::delete_cache_command,
+                commands::window::set_window_size_command,
+                commands::window::reset_window_size_command,
+                commands::window::set_window_title_command,
+                commands::window::set_window_focusable_command,
+                commands::window::set_passthrough_regions_command,
+                commands::window::get_feature_support_command,
+                commands::window::ensure_window_visible_command,
+                commands::ui_state::save_ui_state_command,
+                commands::ui_state::load_ui_state_command,
+                commands::schedule::schedule_action_command,
+                commands::schedule::cancel_scheduled_action_command,
+                commands::snapshot::capture_state_command,
+                commands::snapshot::restore_state_command,
+                commands::auth::open_external_url,
+                commands::auth::open_checkout_portal,
+                commands::auth::generate_qr_command,
+                commands::auth::frontend_ready_command,
+                commands::whispr::launch_whispr_mode_command,
+                commands::whispr::launch_current_provider_command,
+                commands::whispr::navigate_to_dashboard_command,
+                commands::whispr::navigate_back_command,
+                commands::whispr::ack_navigation_command,
+                commands::whispr::get_current_route_command,
+                commands::whispr::verify_providers_command,
+                commands::network::set_proxy_command,
+                commands::whispr::get_last_provider_load_ms_command,
+                commands::views::list_views_command,
+                commands::views::focus_view_command,
+                commands::views::close_view_command,
+                commands::page::get_page_title_command,
+                commands::page::report_page_title_command,
+                commands::screen::get_pixel_color_command,
+                commands::screen::capture_all_monitors_command,
+                commands::recording::detect_screen_recording_command,
+                commands::session::report_provider_url_command,
+                commands::session::clear_last_session_command,
+                commands::logs::export_logs_filtered_command,
+                commands::logs::get_log_path_command,
+                commands::logs::read_recent_logs_command,
+                commands::logs::set_log_level_command,
+                commands::window::open_log_directory_command,
+                commands::remote_config::apply_remote_config_command,
+                commands::typing::start_typing_monitor_command,
+                commands::typing::stop_typing_monitor_command,
+                commands::data_dir::relocate_data_dir_command,
+                commands::window::save_window_slot_command,
+                commands::window::recall_window_slot_command,
+                commands::window::cycle_window_slot_command,
+                commands::self_test::run_self_test_command,
+                commands::shortcuts::check_shortcut_conflicts_command,
+                commands::shortcuts::get_registered_shortcuts_command,
+                commands::shortcuts::start_shortcut_recording_command,
+                commands::shortcuts::report_shortcut_keypress_command,
+                commands::shortcuts::report_text_input_focus_command,
+                commands::settings::update_settings_partial_command,
+                commands::settings::export_settings_command,
+                commands::settings::import_settings_command,
+                commands::window::set_ignore_cursor_events_command,
+                commands::window::save_window_position_command,
+                commands::templates::run_template_command,
+                commands::templates::list_templates_command,
+                commands::templates::save_template_command,
+                commands::templates::delete_template_command,
+            ],
+        ))
+        .on_page_load(|window, payload| {
+            if window.label() == "main" {
+                commands::whispr::finish_provider_load(&window, payload.url());
+                commands::views::install_popup_router(&window, payload.url());
+                commands::session::install_url_reporter(&window, payload.url());
+                commands::session::restore_last_session(&window, payload.url());
+                // Lets text-editing shortcuts step aside while the user types
+                commands::shortcuts::install_text_input_reporter(&window);
+                commands::page::sync_window_title(&window.app_handle());
+            }
+        })
         .setup(|app| {
             info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
             info!("🚀 WhisprGPT Starting...");
             info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
             // Fix protocol registration on Windows (migrates from Electron)
-            #[cfg(target_os = "windows")]
+            // and Linux (macOS uses Info.plist)
+            #[cfg(any(target_os = "windows", target_os = "linux"))]
             {
                 fix_protocol_registration();
             }
 
             let app_handle = app.handle();
 
-            // Windows: Handle command line arguments for deep links
-            #[cfg(target_os = "windows")]
+            // Windows/Linux: Handle command line arguments for deep links
+            #[cfg(any(target_os = "windows", target_os = "linux"))]
             {
                 let args: Vec<String> = std::env::args().collect();
                 debug!("📋 App started with args: {:?}", args);
@@ -178,133 +465,101 @@ fn main() {
                 info!("✅ macOS deep link listener registered");
             }
 
+            if let Some(instance) = instance {
+                let launch_handle = app.handle();
+                single_instance::listen(instance, move |args| {
+                    handle_second_launch(&launch_handle, args)
+                });
+            }
+
+            // Provider pages may only report back (e.g. their title)
+            commands::page::allow_provider_ipc(&app.handle());
+
+            // Debounced disk writes for save_settings_command
+            let saver_handle = app.handle();
+            app.manage(SettingsSaver::new(SAVE_DEBOUNCE, move |settings| {
+                state::settings::save_settings(&saver_handle, settings)
+            }));
+
+            let settings = state::settings::load_settings(&app.handle()).unwrap_or_else(|e| {
+                error!("❌ Failed to load settings, using defaults: {}", e);
+                WhisperSettings::default()
+            });
+
+            // Commands (and shortcuts) see the saved settings from the start
+            // instead of loading them on first use
+            let settings_state = app.state::<Mutex<Option<WhisperSettings>>>();
+            *settings_state.lock().unwrap() = Some(settings.clone());
+
+            app.state::<commands::dnd::DoNotDisturb>()
+                .set(settings.do_not_disturb);
+
+            if settings.restore_last_session {
+                commands::session::queue_session_restore(
+                    &app.handle(),
+                    settings.last_provider_url.as_deref(),
+                );
+            }
+
+            // Opt-in provider reachability check (off by default for privacy)
+            if settings.verify_providers_on_startup {
+                let proxy_url = settings.proxy_url.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = commands::whispr::verify_providers(proxy_url).await {
+                        error!("❌ Provider verification failed: {}", e);
+                    }
+                });
+            }
+
             // Register global hotkeys
             if let Some(window) = app.get_window("main") {
-                let _ = window.set_always_on_top(true);
-                info!("✅ Main window initialized (always-on-top)");
-
-                let mut shortcut_manager = app.global_shortcut_manager();
-                let window_up = window.clone();
-                let window_down = window.clone();
-                let window_left = window.clone();
-                let window_right = window.clone();
-                let window_hide = window.clone();
-                let app_for_ctrlb = app.handle();
-
-
-                // Register shortcuts with graceful error handling
-                let mut registered = 0;
-                let mut failed = 0;
-
-                if shortcut_manager
-                    .register("Ctrl+Up", move || {
-                        if let Ok(pos) = window_up.outer_position() {
-                            let _ = window_up.set_position(tauri::Position::Physical(
-                                tauri::PhysicalPosition {
-                                    x: pos.x,
-                                    y: pos.y - 20,
-                                },
-                            ));
-                        }
-                    })
-                    .is_ok()
-                {
-                    registered += 1;
-                } else {
-                    failed += 1;
+                // Subclassed first: on Windows that's what keeps the extended
+                // styles set below (non-focusable, opacity) once it's shown
+                if let Err(e) = commands::window::watch_display_changes(&window) {
+                    error!("❌ Failed to watch display changes: {}", e);
                 }
 
-                if shortcut_manager
-                    .register("Ctrl+Down", move || {
-                        if let Ok(pos) = window_down.outer_position() {
-                            let _ = window_down.set_position(tauri::Position::Physical(
-                                tauri::PhysicalPosition {
-                                    x: pos.x,
-                                    y: pos.y + 20,
-                                },
-                            ));
-                        }
-                    })
-                    .is_ok()
-                {
-                    registered += 1;
-                } else {
-                    failed += 1;
+                let level = commands::window::window_level_for(&settings);
+                if let Err(e) = commands::window::apply_window_level(&window, level) {
+                    error!("❌ Failed to apply window level: {}", e);
                 }
+                info!("✅ Main window initialized (level: {})", level.as_str());
 
-                if shortcut_manager
-                    .register("Ctrl+Left", move || {
-                        if let Ok(pos) = window_left.outer_position() {
-                            let _ = window_left.set_position(tauri::Position::Physical(
-                                tauri::PhysicalPosition {
-                                    x: pos.x - 20,
-                                    y: pos.y,
-                                },
-                            ));
-                        }
-                    })
-                    .is_ok()
-                {
-                    registered += 1;
-                } else {
-                    failed += 1;
+                // Non-focusable overlays leave keyboard focus with the app underneath
+                if !settings.focusable {
+                    if let Err(e) = commands::window::set_focusable(&window, false) {
+                        error!("❌ Failed to make the window non-focusable: {}", e);
+                    }
                 }
 
-                if shortcut_manager
-                    .register("Ctrl+Right", move || {
-                        if let Ok(pos) = window_right.outer_position() {
-                            let _ = window_right.set_position(tauri::Position::Physical(
-                                tauri::PhysicalPosition {
-                                    x: pos.x + 20,
-                                    y: pos.y,
-                                },
-                            ));
-                        }
-                    })
-                    .is_ok()
-                {
-                    registered += 1;
-                } else {
-                    failed += 1;
+                // Size before position, so the position is clamped with the
+                // right size
+                if let Err(e) = commands::window::restore_window_size(&window, &settings) {
+                    error!("❌ Failed to restore window size: {}", e);
                 }
-                
-                if shortcut_manager
-                    .register("Ctrl+B", move || {
-                        info!("⌨️ Ctrl+B pressed");
-                        if let Err(e) = commands::whispr::navigate_to_dashboard_command(app_for_ctrlb.clone()) {
-                            log::error!("Failed to navigate: {}", e);
-                        }
-                    })
-                    .is_ok()
-                {
-                    registered += 1;
-                } else {
-                    failed += 1;
+                commands::window::watch_window_size(&window);
+
+                if let Err(e) = commands::window::restore_window_position(&window, &settings) {
+                    error!("❌ Failed to restore window position: {}", e);
                 }
 
-                if shortcut_manager
-                    .register("Ctrl+H", move || {
-                        if let Ok(is_visible) = window_hide.is_visible() {
-                            if is_visible {
-                                let _ = window_hide.hide();
-                            } else {
-                                let _ = window_hide.show();
-                            }
-                        }
-                    })
-                    .is_ok()
-                {
-                    registered += 1;
-                } else {
-                    failed += 1;
+                // Bring the window back if it was left on a display that's gone
+                if let Err(e) = commands::window::ensure_window_visible(&window) {
+                    error!("❌ Failed to check window position: {}", e);
                 }
 
-              
+                // Starts hidden (tauri.conf.json) so it doesn't flash up at the
+                // default size and position first
+                if let Err(e) = window.show() {
+                    error!("❌ Failed to show window: {}", e);
+                }
 
-                info!(
-                    "⌨️  Shortcuts: {} registered, {} failed",
-                    registered, failed
-                );
+                // Global shortcuts from the saved settings, so they work before
+                // the webview has loaded (the frontend re-registers them when
+                // they change)
+                if let Err(e) = commands::shortcuts::register_shortcuts(&app.handle(), &settings) {
+                    error!("❌ Failed to register shortcuts: {}", e);
+                }
             }
 
             info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
@@ -312,8 +567,73 @@ fn main() {
             info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(context)
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            // Don't lose a debounced settings save on quit
+            if let tauri::RunEvent::Exit = event {
+                if let Err(e) = app_handle.state::<SettingsSaver>().flush() {
+                    error!("❌ Failed to flush settings on exit: {}", e);
+                }
+            }
+        });
+}
+
+// Another launch handed over its args: bring up the window we already have
+// and route any auth link it was opened with
+fn handle_second_launch(app_handle: &tauri::AppHandle, args: Vec<String>) {
+    info!("🔒 Second launch detected");
+    debug!("📋 Second launch args: {:?}", args);
+
+    if let Some(window) = app_handle.get_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+
+    for arg in args {
+        if arg.starts_with("whisprgpt://") {
+            handle_deep_link(app_handle, arg);
+        }
+    }
+}
+
+// ============================================================================
+// System Tray
+// ============================================================================
+fn system_tray() -> SystemTray {
+    let menu = SystemTrayMenu::new()
+        .add_item(CustomMenuItem::new("show", "Show"))
+        .add_item(CustomMenuItem::new("hide", "Hide"))
+        .add_native_item(SystemTrayMenuItem::Separator)
+        .add_item(CustomMenuItem::new("quit", "Quit"));
+    SystemTray::new().with_menu(menu)
+}
+
+fn handle_tray_event(app_handle: &tauri::AppHandle, event: SystemTrayEvent) {
+    let Some(window) = app_handle.get_window("main") else {
+        if matches!(&event, SystemTrayEvent::MenuItemClick { id, .. } if id == "quit") {
+            app_handle.exit(0);
+        }
+        return;
+    };
+    let opacity = commands::window::saved_opacity(app_handle);
+
+    let result = match event {
+        SystemTrayEvent::LeftClick { .. } => {
+            commands::window::toggle_window_visibility_command(window, opacity)
+        }
+        SystemTrayEvent::MenuItemClick { id, .. } => match id.as_str() {
+            "show" => commands::window::show_window_command(window, opacity),
+            "hide" => commands::window::hide_window_command(window),
+            "quit" => commands::window::close_app_command(window),
+            _ => Ok(()),
+        },
+        _ => Ok(()),
+    };
+    if let Err(e) = result {
+        error!("❌ Tray action failed: {}", e);
+    }
 }
 
 fn handle_deep_link(app_handle: &tauri::AppHandle, url_string: String) {
@@ -322,39 +642,94 @@ fn handle_deep_link(app_handle: &tauri::AppHandle, url_string: String) {
     info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     info!("URL: {}", url_string);
 
-    match extract_auth_params_from_url(&url_string) {
-        Some(params) => {
-            info!("✅ Auth params extracted");
-            info!("   Code length: {}", params.code.len());
-            info!(
-                "   Code starts: {}...",
-                &params.code[..20.min(params.code.len())]
-            );
+    let Some(callback) = auth_callback_from_url(&url_string) else {
+        error!("❌ Could not extract code from URL");
+        info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        return;
+    };
+
+    if let Some(window) = deep_link_window(app_handle) {
+        info!("✅ Main window ready - bringing to front");
+        let _ = window.show();
+        let _ = window.set_focus();
 
-            if let Some(window) = app_handle.get_window("main") {
-                info!("✅ Main window found - bringing to front");
-                let _ = window.show();
-                let _ = window.set_focus();
+        match commands::auth::deliver_auth_callback(&window, callback) {
+            Ok(_) => info!("✅ Auth event delivered"),
+            Err(e) => error!("❌ Failed to deliver auth event: {}", e),
+        }
+    }
+    info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+}
+
+// What an auth link carried: a code (with the state it came back with), or
+// the provider turning the sign-in down
+fn auth_callback_from_url(url_string: &str) -> Option<commands::auth::AuthCallback> {
+    if let Some(error) = extract_url_param(url_string, "error") {
+        let description = extract_url_param(url_string, "error_description");
+        error!(
+            "❌ Sign-in failed: {} ({})",
+            error,
+            description.as_deref().unwrap_or("no description")
+        );
+        return Some(commands::auth::AuthCallback::Failed { error, description });
+    }
+
+    let params = extract_auth_params_from_url(url_string)?;
+    info!("✅ Auth params extracted");
+    info!("   Code length: {}", params.code.len());
+    info!("   Code starts: {}...", code_preview(&params.code));
+    Some(commands::auth::AuthCallback::Code {
+        code: params.code,
+        token_type: params.token_type,
+        refresh_token: extract_url_param(url_string, "refresh_token"),
+        state: extract_url_param(url_string, "state"),
+    })
+}
 
-                info!("🔄 Emitting 'auth-callback' event...");
-                match commands::auth::handle_auth_callback(&window, params.code) {
-                    Ok(_) => info!("✅ Event emitted successfully"),
-                    Err(e) => error!("❌ Failed to emit event: {}", e),
+// The window an auth link is delivered to. If it was closed, it's built again
+// rather than dropping the sign-in.
+fn deep_link_window(app_handle: &tauri::AppHandle) -> Option<tauri::Window> {
+    match commands::window::require_main_window(app_handle) {
+        Ok(window) => Some(window),
+        Err(e) => {
+            warn!("⚠️  {} - recreating it for the deep link", e);
+            // Hold the auth events until the new webview listens
+            app_handle.state::<commands::auth::AuthEventQueue>().reset();
+            match commands::window::recreate_main_window(app_handle) {
+                Ok(window) => Some(window),
+                Err(e) => {
+                    error!("❌ Failed to recreate main window: {}", e);
+                    None
                 }
-            } else {
-                error!("❌ Main window not found!");
             }
         }
-        None => {
-            error!("❌ Could not extract code from URL");
-        }
     }
-    info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 }
 
 #[derive(Debug)]
 struct AuthParams {
     code: String,
+    token_type: TokenType,
+}
+
+const CODE_PREVIEW_CHARS: usize = 20;
+
+// The first characters of a code, for the log. Cut on a char boundary: the
+// code isn't validated yet and may not be ASCII.
+fn code_preview(code: &str) -> &str {
+    match code.char_indices().nth(CODE_PREVIEW_CHARS) {
+        Some((end, _)) => &code[..end],
+        None => code,
+    }
+}
+
+// Authorization-code flows send `code`; implicit flows send `access_token`
+fn token_type_for(key: &str) -> Option<TokenType> {
+    match key {
+        "code" => Some(TokenType::Code),
+        "access_token" => Some(TokenType::AccessToken),
+        _ => None,
+    }
 }
 
 fn extract_auth_params_from_url(url_string: &str) -> Option<AuthParams> {
@@ -362,51 +737,283 @@ fn extract_auth_params_from_url(url_string: &str) -> Option<AuthParams> {
         Ok(url) => {
             // Check query parameters
             for (key, value) in url.query_pairs() {
-                if key == "code" {
+                if let Some(token_type) = token_type_for(&key) {
                     return Some(AuthParams {
                         code: value.to_string(),
+                        token_type,
                     });
                 }
             }
             // Check fragment
-            if let Some(fragment) = url.fragment() {
-                for param in fragment.split('&') {
-                    let parts: Vec<&str> = param.split('=').collect();
-                    if parts.len() == 2 && (parts[0] == "code" || parts[0] == "access_token") {
-                        return Some(AuthParams {
-                            code: parts[1].to_string(),
-                        });
-                    }
-                }
-            }
-            None
+            url.fragment().and_then(find_auth_param)
         }
         Err(_) => extract_auth_params_manually(url_string),
     }
 }
 
+// A query or fragment parameter, decoded
+fn extract_url_param(url_string: &str, key: &str) -> Option<String> {
+    let url = Url::parse(url_string).ok()?;
+    let fragment = url::form_urlencoded::parse(url.fragment().unwrap_or("").as_bytes());
+    url.query_pairs()
+        .chain(fragment)
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.into_owned())
+}
+
 fn extract_auth_params_manually(url: &str) -> Option<AuthParams> {
-    if let Some(query_start) = url.find('?') {
-        let query = &url[query_start + 1..];
-        for param in query.split('&') {
-            let kv: Vec<&str> = param.split('=').collect();
-            if kv.len() == 2 && kv[0] == "code" {
-                return Some(AuthParams {
-                    code: kv[1].to_string(),
-                });
+    let (rest, fragment) = match url.split_once('#') {
+        Some((rest, fragment)) => (rest, Some(fragment)),
+        None => (url, None),
+    };
+    let query = rest.split_once('?').map(|(_, query)| query);
+
+    query
+        .and_then(find_auth_param)
+        .or_else(|| fragment.and_then(find_auth_param))
+}
+
+// The code or token in `a=1&b=2` style parameters, percent-decoded. Values
+// are split at the first '=' only, so base64 padding stays part of the code.
+fn find_auth_param(params: &str) -> Option<AuthParams> {
+    params.split('&').find_map(|param| {
+        let (key, value) = param.split_once('=')?;
+        Some(AuthParams {
+            code: percent_decode_str(value).decode_utf8_lossy().into_owned(),
+            token_type: token_type_for(key)?,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A #[tauri::command] missing from generate_handler! only shows up as
+    // "command not found" at runtime, so check the sources for it
+    #[test]
+    fn every_command_is_registered() {
+        let main_source = include_str!("main.rs");
+        let commands_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/commands");
+
+        let mut missing = Vec::new();
+        for entry in std::fs::read_dir(commands_dir).unwrap() {
+            let path = entry.unwrap().path();
+            let module = path.file_stem().unwrap().to_string_lossy().into_owned();
+            let source = std::fs::read_to_string(&path).unwrap();
+
+            let mut lines = source.lines();
+            while let Some(line) = lines.next() {
+                if line != "#[tauri::command]" {
+                    continue;
+                }
+                let signature = lines.find(|line| line.contains("fn ")).unwrap();
+                let name: String = signature[signature.find("fn ").unwrap() + 3..]
+                    .chars()
+                    .take_while(|c| c.is_alphanumeric() || *c == '_')
+                    .collect();
+                let registration = format!("commands::{}::{},", module, name);
+                if !main_source.contains(&registration) {
+                    missing.push(registration);
+                }
             }
         }
+        assert!(
+            missing.is_empty(),
+            "Not in generate_handler!: {:?}",
+            missing
+        );
     }
-    if let Some(hash_start) = url.find('#') {
-        let hash = &url[hash_start + 1..];
-        for param in hash.split('&') {
-            let kv: Vec<&str> = param.split('=').collect();
-            if kv.len() == 2 && (kv[0] == "code" || kv[0] == "access_token") {
-                return Some(AuthParams {
-                    code: kv[1].to_string(),
-                });
+
+    // The app was closed when the browser handed the sign-in back: the link
+    // starts a fresh instance, and the code has to wait for the webview
+    #[test]
+    fn cold_start_auth_links_reach_the_frontend() {
+        use commands::auth::{AuthEvent, AuthEventQueue, OAuthState};
+
+        let dir = std::env::temp_dir().join(format!("whisprgpt-cold-start-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // The run that opened the sign-in page
+        let state = OAuthState::persisted_in(&dir).begin();
+
+        let oauth = OAuthState::persisted_in(&dir);
+        let queue = AuthEventQueue::default();
+        let code = "0f8fad5b-d9cb-469f-a165-70867728950e";
+        let url = format!("whisprgpt://callback?code={}&state={}", code, state);
+
+        let mut sent = Vec::new();
+        let callback = auth_callback_from_url(&url).unwrap();
+        commands::auth::route_auth_callback(&oauth, callback, |event| {
+            commands::auth::emit_or_hold(&queue, event, |event| {
+                sent.push(event);
+                Ok(())
+            })
+        })
+        .unwrap();
+        assert!(sent.is_empty());
+
+        commands::auth::flush_auth_events(&queue, |event| {
+            sent.push(event);
+            Ok(())
+        })
+        .unwrap();
+        match sent.as_slice() {
+            [AuthEvent::Callback(payload)] => assert_eq!(payload.code, code),
+            other => panic!("expected an auth-callback, got {:?}", other),
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    // Base64 codes keep their padding and slashes all the way to the frontend
+    #[test]
+    fn padded_codes_reach_the_frontend() {
+        use commands::auth::{AuthEvent, OAuthState};
+
+        let oauth = OAuthState::default();
+        let state = oauth.begin();
+        let url = format!(
+            "whisprgpt://callback?code=q%2B8%2FAbCdEfGhIjKlMnOpQrStUvWx%3D%3D&state={}",
+            state
+        );
+
+        let mut sent = Vec::new();
+        let callback = auth_callback_from_url(&url).unwrap();
+        commands::auth::route_auth_callback(&oauth, callback, |event| {
+            sent.push(event);
+            Ok(())
+        })
+        .unwrap();
+        match sent.as_slice() {
+            [AuthEvent::Callback(payload)] => {
+                assert_eq!(payload.code, "q+8/AbCdEfGhIjKlMnOpQrStUvWx==")
             }
+            other => panic!("expected an auth-callback, got {:?}", other),
         }
     }
-    None
+
+    #[test]
+    fn implicit_flow_links_carry_the_refresh_token() {
+        assert_eq!(
+            auth_callback_from_url(
+                "whisprgpt://callback#access_token=tok456&refresh_token=ref789&expires_in=3600&state=s1",
+            ),
+            Some(commands::auth::AuthCallback::Code {
+                code: "tok456".to_string(),
+                token_type: TokenType::AccessToken,
+                refresh_token: Some("ref789".to_string()),
+                state: Some("s1".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn provider_errors_are_read_from_query_and_fragment() {
+        let url = "whisprgpt://callback?error=access_denied&error_description=User+denied%20access";
+        assert_eq!(
+            extract_url_param(url, "error").as_deref(),
+            Some("access_denied")
+        );
+        assert_eq!(
+            extract_url_param(url, "error_description").as_deref(),
+            Some("User denied access")
+        );
+        assert!(extract_auth_params_from_url(url).is_none());
+
+        let url = "whisprgpt://callback?state=abc#error=server_error";
+        assert_eq!(
+            extract_url_param(url, "error").as_deref(),
+            Some("server_error")
+        );
+        assert_eq!(extract_url_param(url, "error_description"), None);
+
+        assert_eq!(
+            extract_url_param("whisprgpt://callback?code=abc", "error"),
+            None
+        );
+    }
+
+    #[test]
+    fn auth_params_keep_padding_and_are_decoded() {
+        for url in [
+            "whisprgpt://callback?code=YWJjZA==",
+            "whisprgpt://callback#code=YWJjZA==",
+        ] {
+            assert_eq!(extract_auth_params_from_url(url).unwrap().code, "YWJjZA==");
+        }
+
+        // Percent-encoded values come back decoded
+        for url in [
+            "whisprgpt://callback?code=a%2Fb%3D",
+            "whisprgpt://callback#code=a%2Fb%3D",
+        ] {
+            assert_eq!(extract_auth_params_from_url(url).unwrap().code, "a/b=");
+        }
+
+        // Query wins over fragment, and neither leaks into the other
+        let url = "whisprgpt://callback?code=from-query&state=s1#access_token=from-fragment";
+        let params = extract_auth_params_from_url(url).unwrap();
+        assert_eq!(params.code, "from-query");
+        assert_eq!(params.token_type, TokenType::Code);
+        let url = "whisprgpt://callback?state=s1#code=from-fragment";
+        let params = extract_auth_params_from_url(url).unwrap();
+        assert_eq!(params.code, "from-fragment");
+    }
+
+    #[test]
+    fn manual_parser_handles_padding_encoding_and_fragments() {
+        let params = extract_auth_params_manually("not a url?code=YWJjZA==&state=s1").unwrap();
+        assert_eq!(params.code, "YWJjZA==");
+
+        let params = extract_auth_params_manually("not a url?code=a%2Fb").unwrap();
+        assert_eq!(params.code, "a/b");
+
+        let params = extract_auth_params_manually("not a url?code=abc#access_token=xyz").unwrap();
+        assert_eq!(params.code, "abc");
+        assert_eq!(params.token_type, TokenType::Code);
+
+        let params = extract_auth_params_manually("not a url?state=s1#access_token=x=y").unwrap();
+        assert_eq!(params.code, "x=y");
+        assert_eq!(params.token_type, TokenType::AccessToken);
+
+        assert!(extract_auth_params_manually("not a url?state=s1#code").is_none());
+        assert!(extract_auth_params_manually("not a url").is_none());
+    }
+
+    #[test]
+    fn access_tokens_are_read_from_query_and_fragment() {
+        let params = extract_auth_params_from_url(
+            "whisprgpt://callback?access_token=tok123&token_type=bearer",
+        )
+        .unwrap();
+        assert_eq!(params.code, "tok123");
+        assert_eq!(params.token_type, TokenType::AccessToken);
+
+        let params = extract_auth_params_from_url(
+            "whisprgpt://callback#access_token=tok456&expires_in=3600",
+        )
+        .unwrap();
+        assert_eq!(params.code, "tok456");
+        assert_eq!(params.token_type, TokenType::AccessToken);
+
+        let params = extract_auth_params_from_url("whisprgpt://callback?code=abc123").unwrap();
+        assert_eq!(params.code, "abc123");
+        assert_eq!(params.token_type, TokenType::Code);
+    }
+
+    #[test]
+    fn code_previews_stop_on_a_char_boundary() {
+        assert_eq!(code_preview(""), "");
+        assert_eq!(code_preview("abc123"), "abc123");
+        assert_eq!(
+            code_preview("7c9e6679-7425-40de-944b-e07fc1f90ae7"),
+            "7c9e6679-7425-40de-9"
+        );
+        // 19 ASCII bytes, then a 3-byte char straddling byte 20
+        assert_eq!(
+            code_preview("aaaaaaaaaaaaaaaaaaa€€"),
+            "aaaaaaaaaaaaaaaaaaa€"
+        );
+    }
 }
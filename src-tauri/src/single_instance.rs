@@ -0,0 +1,194 @@
+// single_instance.rs
+// Keeps a single running instance. The first instance listens on a loopback
+// port recorded in PORT_FILE (in the per-user app data dir); a later launch
+// (e.g. the OS opening a whisprgpt:// auth link) hands its argv over to it
+// and exits, so the OAuth callback lands in the process that's waiting for it.
+//
+// Loopback is open to every local user and process, so PORT_FILE also holds
+// a random token from this launch. Only someone who can read the file (the
+// same user) can hand anything over.
+
+use log::warn;
+use rand::distributions::{Alphanumeric, DistString};
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::Path;
+use std::time::Duration;
+
+pub const PORT_FILE: &str = "instance.port";
+
+// Sent first on every connection with the token, so an unrelated program that
+// happens to own a stale port is never mistaken for a running instance
+const HANDSHAKE: &str = "whisprgpt-instance/2";
+const ACCEPTED: &str = "ok";
+const TOKEN_LEN: usize = 32;
+
+// Longest handshake and argv lines read from a connection
+const MAX_HANDSHAKE_BYTES: usize = 128;
+const MAX_ARGS_BYTES: usize = 64 * 1024;
+
+const FORWARD_TIMEOUT: Duration = Duration::from_secs(2);
+
+// ============================================================================
+// Second Launch
+// ============================================================================
+// Returns true if a running instance took `args`; the caller should exit.
+// Anything else means this is the first instance: false if there's no port
+// file, an error worth logging if one was left behind (nothing listening, no
+// reply). Runs before logging is set up, so it doesn't log itself.
+pub fn forward_to_running_instance(dir: &Path, args: &[String]) -> io::Result<bool> {
+    match try_forward(dir, args) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+fn try_forward(dir: &Path, args: &[String]) -> io::Result<()> {
+    let contents = fs::read_to_string(dir.join(PORT_FILE))?;
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "Invalid port file");
+    let (port, token) = contents.trim().split_once('\n').ok_or_else(invalid)?;
+    let port: u16 = port.trim().parse().map_err(|_| invalid())?;
+
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let mut stream = TcpStream::connect_timeout(&address, FORWARD_TIMEOUT)?;
+    stream.set_read_timeout(Some(FORWARD_TIMEOUT))?;
+    stream.set_write_timeout(Some(FORWARD_TIMEOUT))?;
+
+    let args = serde_json::to_string(args)?;
+    writeln!(stream, "{} {}\n{}", HANDSHAKE, token.trim(), args)?;
+
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply)?;
+    if reply.trim() != ACCEPTED {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Unexpected reply from the running instance",
+        ));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// First Instance
+// ============================================================================
+// Claims the instance port. Two launches racing each other can both end up
+// here; that's the same as before this existed.
+pub fn bind(dir: &Path) -> io::Result<Instance> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    let token = Alphanumeric.sample_string(&mut rand::thread_rng(), TOKEN_LEN);
+    fs::create_dir_all(dir)?;
+    write_private(
+        &dir.join(PORT_FILE),
+        &format!("{}\n{}", listener.local_addr()?.port(), token),
+    )?;
+    Ok(Instance { listener, token })
+}
+
+// Readable by this user only, wherever the data dir is
+fn write_private(path: &Path, contents: &str) -> io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // mode() only applies to new files
+        if path.exists() {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+    }
+    options.open(path)?.write_all(contents.as_bytes())
+}
+
+// Calls `on_launch` with the argv of every later launch, on a background thread
+pub fn listen(instance: Instance, on_launch: impl Fn(Vec<String>) + Send + 'static) {
+    let Instance { listener, token } = instance;
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let result = stream.and_then(|stream| {
+                stream.set_read_timeout(Some(FORWARD_TIMEOUT))?;
+                read_launch(stream, &token)
+            });
+            match result {
+                Ok(args) => on_launch(args),
+                Err(e) => warn!("⚠️  Ignored a connection on the instance port: {}", e),
+            }
+        }
+    });
+}
+
+fn read_launch(stream: TcpStream, token: &str) -> io::Result<Vec<String>> {
+    let mut reader = BufReader::new(stream);
+
+    let handshake = read_line_capped(&mut reader, MAX_HANDSHAKE_BYTES)?;
+    if handshake.trim_end() != format!("{} {}", HANDSHAKE, token) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Bad handshake"));
+    }
+
+    let args = read_line_capped(&mut reader, MAX_ARGS_BYTES)?;
+    let args: Vec<String> = serde_json::from_str(&args)?;
+
+    writeln!(reader.get_mut(), "{}", ACCEPTED)?;
+    Ok(args)
+}
+
+fn read_line_capped(reader: &mut impl BufRead, max: usize) -> io::Result<String> {
+    let mut line = String::new();
+    reader.take(max as u64 + 1).read_line(&mut line)?;
+    if line.len() > max {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Line too long"));
+    }
+    Ok(line)
+}
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+// The claimed port and the token later launches must present
+pub struct Instance {
+    listener: TcpListener,
+    token: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn second_launch_hands_its_args_to_the_first() {
+        let dir = std::env::temp_dir().join(format!("whisprgpt-instance-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        // Nothing running yet
+        assert!(!forward_to_running_instance(&dir, &[]).unwrap());
+
+        let (sender, receiver) = mpsc::channel();
+        listen(bind(&dir).unwrap(), move |args| sender.send(args).unwrap());
+
+        let args = vec!["whisprgpt://auth?code=abc".to_string()];
+        assert!(forward_to_running_instance(&dir, &args).unwrap());
+        assert_eq!(receiver.recv_timeout(FORWARD_TIMEOUT).unwrap(), args);
+
+        // Oversized argv is refused rather than read into memory
+        let huge = vec!["a".repeat(MAX_ARGS_BYTES)];
+        assert!(forward_to_running_instance(&dir, &huge).is_err());
+
+        // The right port without the token (another local user or process)
+        let contents = fs::read_to_string(dir.join(PORT_FILE)).unwrap();
+        let port = contents.lines().next().unwrap();
+        fs::write(dir.join(PORT_FILE), format!("{}\nguessed", port)).unwrap();
+        assert!(forward_to_running_instance(&dir, &args).is_err());
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+
+        // A stale port file pointing at something that isn't us
+        let stranger = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let port = stranger.local_addr().unwrap().port();
+        fs::write(dir.join(PORT_FILE), format!("{}\ntoken", port)).unwrap();
+        drop(stranger);
+        assert!(forward_to_running_instance(&dir, &args).is_err());
+    }
+}
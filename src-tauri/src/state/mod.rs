@@ -3,6 +3,8 @@
 // Think of it like an index.ts file that exports everything.
 
 pub mod settings;
+pub mod settings_saver;
+pub mod ui_state;
 
 // RUST CONCEPT: "pub mod" makes the module public
 // This allows other parts of your app to import from state::settings
\ No newline at end of file
@@ -2,11 +2,29 @@
 // This file handles reading/writing settings to disk.
 // It replaces electron-store from your Electron app.
 
-use crate::types::WhisperSettings;
+use crate::types::{WhisperSettings, SETTINGS_VERSION};
+use log::{info, warn};
+use serde_json::Value;
 use std::fs;
-use std::path::PathBuf;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tauri::AppHandle;
 
+pub const SETTINGS_FILE: &str = "settings.json";
+
+// Lives in the *default* app data dir and holds the path of the data dir the
+// user moved everything to (see relocate_data_dir_command)
+pub const DATA_DIR_POINTER: &str = "data_dir.txt";
+
+// Backoff between attempts when settings.json is locked (~500ms in total)
+const LOCKED_RETRY_DELAYS: [Duration; 4] = [
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(150),
+    Duration::from_millis(200),
+];
+
 // ============================================================================
 // RUST CONCEPT: Result<T, E>
 // ============================================================================
@@ -17,17 +35,14 @@ use tauri::AppHandle;
 // This is like TypeScript's: Promise<WhisperSettings> that might throw
 
 // ============================================================================
-// Get the path to the settings file
+// Get the app data directory (created if missing)
 // ============================================================================
-fn get_settings_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+pub fn get_app_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
     // RUST CONCEPT: &AppHandle is a "reference" (like passing by reference in C++)
     // The & means we're borrowing the AppHandle, not taking ownership
     
     // Get the app's data directory (like app.getPath('userData') in Electron)
-    let app_dir = app_handle
-        .path_resolver()
-        .app_data_dir()
-        .ok_or_else(|| "Failed to get app data directory".to_string())?;
+    let app_dir = resolve_app_dir(&get_default_app_dir(app_handle)?);
     
     // RUST CONCEPT: ? operator
     // The ? at the end is like "await" + automatic error handling
@@ -37,16 +52,103 @@ fn get_settings_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
     fs::create_dir_all(&app_dir)
         .map_err(|e| format!("Failed to create app directory: {}", e))?;
     
-    // Return the full path to settings.json
-    Ok(app_dir.join("settings.json"))
+    Ok(app_dir)
+}
+
+pub fn get_default_app_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    app_handle
+        .path_resolver()
+        .app_data_dir()
+        .ok_or_else(|| "Failed to get app data directory".to_string())
+}
+
+// The relocated data dir if the user moved it, otherwise the default one
+pub fn resolve_app_dir(default_dir: &Path) -> PathBuf {
+    match fs::read_to_string(default_dir.join(DATA_DIR_POINTER)) {
+        Ok(path) if !path.trim().is_empty() => PathBuf::from(path.trim()),
+        _ => default_dir.to_path_buf(),
+    }
+}
+
+// ============================================================================
+// Get the path to the settings file
+// ============================================================================
+fn get_settings_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    Ok(get_app_dir(app_handle)?.join(SETTINGS_FILE))
+}
+
+// ============================================================================
+// Write a file atomically
+// ============================================================================
+// Writes to "<name>.tmp" next to the target and renames it over the real file,
+// so a crash mid-write never leaves a truncated file behind.
+pub fn write_atomic(path: &Path, contents: &str) -> Result<(), String> {
+    replace_file(path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn replace_file(path: &Path, contents: &str) -> io::Result<()> {
+    let mut tmp_name = path.as_os_str().to_owned();
+    tmp_name.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_name);
+
+    fs::write(&tmp_path, contents)?;
+    fs::rename(&tmp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&tmp_path);
+    })
+}
+
+// ============================================================================
+// Retry writes on transient locks
+// ============================================================================
+// On Windows, antivirus scanners and backup tools briefly lock files they
+// look at; a write during that window fails with a sharing violation even
+// though it would succeed a moment later. `is_locked` is is_lock_error
+// outside of tests.
+fn retry_while_locked<T>(
+    delays: &[Duration],
+    is_locked: impl Fn(&io::Error) -> bool,
+    mut write: impl FnMut() -> io::Result<T>,
+) -> io::Result<T> {
+    let mut delays = delays.iter();
+    loop {
+        match write() {
+            Err(e) if is_locked(&e) => match delays.next() {
+                Some(delay) => {
+                    warn!(
+                        "⚠️  File is locked, retrying in {}ms: {}",
+                        delay.as_millis(),
+                        e
+                    );
+                    std::thread::sleep(*delay);
+                }
+                None => return Err(e),
+            },
+            result => return result,
+        }
+    }
+}
+
+// Only Windows locks files against other programs. A plain permission error
+// (read-only directory, bad ACL) isn't a lock and won't go away by waiting.
+fn is_lock_error(error: &io::Error) -> bool {
+    cfg!(windows) && is_lock_code(error.raw_os_error())
+}
+
+// ERROR_SHARING_VIOLATION / ERROR_LOCK_VIOLATION
+fn is_lock_code(code: Option<i32>) -> bool {
+    matches!(code, Some(32 | 33))
 }
 
 // ============================================================================
 // Load settings from disk
 // ============================================================================
 pub fn load_settings(app_handle: &AppHandle) -> Result<WhisperSettings, String> {
-    let settings_path = get_settings_path(app_handle)?;
-    
+    load_settings_from(&get_settings_path(app_handle)?)
+}
+
+// Same as load_settings, for code that runs before the app (and its
+// AppHandle) exists, e.g. startup options the webview needs at creation
+pub fn load_settings_from(settings_path: &Path) -> Result<WhisperSettings, String> {
     // Check if the file exists
     if !settings_path.exists() {
         // If no file exists, return default settings
@@ -56,15 +158,96 @@ pub fn load_settings(app_handle: &AppHandle) -> Result<WhisperSettings, String>
     
     // Read the file contents
     // RUST CONCEPT: fs::read_to_string() is like fs.readFileSync() in Node.js
-    let contents = fs::read_to_string(&settings_path)
+    let contents = fs::read_to_string(settings_path)
         .map_err(|e| format!("Failed to read settings file: {}", e))?;
     
-    // Parse JSON into WhisperSettings struct
+    // Parse JSON into WhisperSettings struct, bringing older files up to date first
     // RUST CONCEPT: serde_json::from_str() is like JSON.parse() in JavaScript
-    let settings: WhisperSettings = serde_json::from_str(&contents)
-        .map_err(|e| format!("Failed to parse settings JSON: {}", e))?;
-    
-    Ok(settings)
+    let settings = serde_json::from_str(&contents)
+        .map_err(|e| e.to_string())
+        .and_then(migrate_settings)
+        .and_then(|raw| serde_json::from_value(raw).map_err(|e| e.to_string()));
+
+    match settings {
+        Ok(settings) => Ok(settings),
+        Err(e) => {
+            warn!(
+                "⚠️  Settings file is corrupted ({}), starting with defaults",
+                e
+            );
+            set_aside_corrupt_file(settings_path);
+            Ok(WhisperSettings::default())
+        }
+    }
+}
+
+// Keeps an unreadable settings file as settings.json.corrupt-<unix time> for
+// debugging, so the next save doesn't overwrite it
+fn set_aside_corrupt_file(settings_path: &Path) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let mut corrupt_name = settings_path.as_os_str().to_owned();
+    corrupt_name.push(format!(".corrupt-{}", timestamp));
+    let corrupt_path = PathBuf::from(corrupt_name);
+
+    match fs::rename(settings_path, &corrupt_path) {
+        Ok(()) => warn!("⚠️  Corrupted settings kept at {}", corrupt_path.display()),
+        Err(e) => warn!("⚠️  Failed to set aside corrupted settings: {}", e),
+    }
+}
+
+// ============================================================================
+// Migrate settings from older versions
+// ============================================================================
+// Works on the raw JSON so files that are missing required fields still load:
+// every missing field gets its default, then the version is bumped. Changes
+// that need more than that (renames, new formats) go in a step per version.
+// Version 2: new default shortcuts are merged into the saved map key by key,
+// on every load, so each one added later reaches upgrading users too.
+pub fn migrate_settings(mut raw: Value) -> Result<Value, String> {
+    let fields = raw
+        .as_object_mut()
+        .ok_or_else(|| "Settings file is not a JSON object".to_string())?;
+
+    let version = fields.get("version").and_then(Value::as_u64).unwrap_or(0);
+    if version > SETTINGS_VERSION as u64 {
+        warn!(
+            "⚠️  Settings are from a newer version ({}), unknown fields will be dropped",
+            version
+        );
+        return Ok(raw);
+    }
+
+    let defaults = serde_json::to_value(WhisperSettings::default())
+        .map_err(|e| format!("Failed to serialize default settings: {}", e))?;
+    if let Value::Object(defaults) = defaults {
+        for (field, value) in defaults {
+            match (fields.get_mut(&field), value) {
+                (Some(Value::Object(shortcuts)), Value::Object(default_shortcuts))
+                    if field == "shortcuts" =>
+                {
+                    for (key, entry) in default_shortcuts {
+                        shortcuts.entry(key).or_insert(entry);
+                    }
+                }
+                (Some(_), _) => {}
+                (None, value) => {
+                    fields.insert(field, value);
+                }
+            }
+        }
+    }
+
+    if version < SETTINGS_VERSION as u64 {
+        info!(
+            "🔄 Migrated settings from version {} to {}",
+            version, SETTINGS_VERSION
+        );
+    }
+    fields.insert("version".to_string(), Value::from(SETTINGS_VERSION));
+    Ok(raw)
 }
 
 // ============================================================================
@@ -85,30 +268,29 @@ pub fn save_settings(
     let json = serde_json::to_string_pretty(settings)
         .map_err(|e| format!("Failed to serialize settings: {}", e))?;
     
-    // Write to file
-    // RUST CONCEPT: fs::write() is like fs.writeFileSync() in Node.js
-    fs::write(&settings_path, json)
-        .map_err(|e| format!("Failed to write settings file: {}", e))?;
+    // Write to file - via settings.json.tmp + rename, so a crash mid-write
+    // leaves the previous settings intact instead of a truncated file
+    let write = || replace_file(&settings_path, &json);
+    retry_while_locked(&LOCKED_RETRY_DELAYS, is_lock_error, write).map_err(|e| {
+        if is_lock_error(&e) {
+            format!(
+                "Settings file is locked by another program ({}). Your changes were not saved - \
+                 if this keeps happening, exclude {} from your antivirus scanner and try again.",
+                e,
+                settings_path.display()
+            )
+        } else {
+            format!("Failed to write settings file: {}", e)
+        }
+    })?;
     
     Ok(())
 }
 
-// ============================================================================
-// Reset settings to default
-// ============================================================================
-pub fn reset_settings(app_handle: &AppHandle) -> Result<WhisperSettings, String> {
-    let default_settings = WhisperSettings::default();
-    
-    // Save the default settings to disk
-    save_settings(app_handle, &default_settings)?;
-    
-    Ok(default_settings)
-}
-
 // ============================================================================
 // SUMMARY FOR JAVASCRIPT DEVELOPERS:
 // ============================================================================
-// This file provides three functions:
+// This file provides two functions:
 // 
 // 1. load_settings(app_handle) -> Result<WhisperSettings, String>
 //    - Like: async function loadSettings(): Promise<WhisperSettings>
@@ -118,12 +300,186 @@ pub fn reset_settings(app_handle: &AppHandle) -> Result<WhisperSettings, String>
 //    - Like: async function saveSettings(settings: WhisperSettings): Promise<void>
 //    - Writes settings to settings.json
 //
-// 3. reset_settings(app_handle) -> Result<WhisperSettings, String>
-//    - Like: async function resetSettings(): Promise<WhisperSettings>
-//    - Resets to default and saves
-//
 // Key differences from JavaScript:
 // - No try/catch - Rust uses Result<T, E> for error handling
 // - No async/await (yet) - these are synchronous file operations
 // - Explicit error handling with ? operator
-// - Type safety enforced at compile time
\ No newline at end of file
+// - Type safety enforced at compile time
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn interrupted_writes_keep_the_previous_file() {
+        let dir = std::env::temp_dir().join(format!("whisprgpt-atomic-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(SETTINGS_FILE);
+
+        let mut good = WhisperSettings::default();
+        good.do_not_disturb = true;
+        write_atomic(&path, &serde_json::to_string_pretty(&good).unwrap()).unwrap();
+
+        // The process was killed halfway through writing the next version
+        let partial = serde_json::to_string_pretty(&WhisperSettings::default()).unwrap();
+        fs::write(dir.join("settings.json.tmp"), &partial[..partial.len() / 2]).unwrap();
+
+        assert!(load_settings_from(&path).unwrap().do_not_disturb);
+
+        // The next save goes through and cleans up the leftover
+        write_atomic(&path, &partial).unwrap();
+        assert!(!load_settings_from(&path).unwrap().do_not_disturb);
+        assert!(!dir.join("settings.json.tmp").exists());
+    }
+
+    #[test]
+    fn corrupted_settings_fall_back_to_defaults() {
+        let dir = std::env::temp_dir().join(format!("whisprgpt-corrupt-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(SETTINGS_FILE);
+        fs::write(&path, r#"{"do_not_disturb": true, "shortcuts": {"#).unwrap();
+
+        let settings = load_settings_from(&path).unwrap();
+        assert!(!settings.do_not_disturb);
+        assert!(!path.exists());
+
+        let kept: Vec<PathBuf> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(kept.len(), 1);
+        let name = kept[0].file_name().unwrap().to_string_lossy().into_owned();
+        assert!(name.starts_with("settings.json.corrupt-"));
+        assert_eq!(
+            fs::read_to_string(&kept[0]).unwrap(),
+            r#"{"do_not_disturb": true, "shortcuts": {"#
+        );
+    }
+
+    #[test]
+    fn version_0_settings_are_migrated() {
+        // Written before the version field and most other fields existed
+        let v0 = serde_json::json!({
+            "llm": "grok",
+            "opacity": 0.8,
+            "shortcuts": {}
+        });
+
+        let migrated = migrate_settings(v0).unwrap();
+        assert_eq!(migrated["version"], SETTINGS_VERSION);
+
+        let settings: WhisperSettings = serde_json::from_value(migrated).unwrap();
+        let defaults = WhisperSettings::default();
+        assert_eq!(settings.llm, "grok");
+        assert_eq!(settings.opacity, 0.8);
+        assert_eq!(settings.system_prompt, defaults.system_prompt);
+        assert_eq!(settings.screen_width, defaults.screen_width);
+        assert_eq!(settings.shortcuts.len(), defaults.shortcuts.len());
+
+        // Current files come through unchanged
+        let current = serde_json::to_value(&defaults).unwrap();
+        assert_eq!(migrate_settings(current.clone()).unwrap(), current);
+        assert!(migrate_settings(serde_json::json!([1, 2])).is_err());
+    }
+
+    #[test]
+    fn new_default_shortcuts_reach_old_settings_files() {
+        let defaults = WhisperSettings::default();
+        let mut screenshot = serde_json::to_value(&defaults.shortcuts["screenshot"]).unwrap();
+        screenshot["custom_shortcut"] = serde_json::json!({ "windows": "Ctrl + Shift + S" });
+
+        // A version 1 file, saved before "panic" and friends existed
+        let v1 = serde_json::json!({
+            "version": 1,
+            "shortcuts": { "screenshot": screenshot }
+        });
+
+        let settings: WhisperSettings =
+            serde_json::from_value(migrate_settings(v1).unwrap()).unwrap();
+        assert_eq!(settings.version, SETTINGS_VERSION);
+        assert_eq!(settings.shortcuts.len(), defaults.shortcuts.len());
+        assert_eq!(
+            settings.shortcuts["panic"].title,
+            defaults.shortcuts["panic"].title
+        );
+
+        // Existing entries are left as they were
+        let custom = settings.shortcuts["screenshot"].custom_shortcut.as_ref();
+        assert_eq!(
+            custom.and_then(|custom| custom.windows.as_deref()),
+            Some("Ctrl + Shift + S")
+        );
+    }
+
+    #[test]
+    fn shortcuts_added_since_release_reach_upgrading_users() {
+        let defaults = WhisperSettings::default();
+        let added = [
+            "back",
+            "panic",
+            "always-on-top",
+            "next-monitor",
+            "opacity-up",
+            "opacity-down",
+        ];
+
+        // The shortcuts map as saved before any of them existed
+        let mut old = serde_json::to_value(&defaults).unwrap();
+        old["version"] = serde_json::json!(1);
+        let shortcuts = old["shortcuts"].as_object_mut().unwrap();
+        for key in added {
+            assert!(shortcuts.remove(key).is_some(), "{} is a default", key);
+        }
+
+        let settings: WhisperSettings =
+            serde_json::from_value(migrate_settings(old).unwrap()).unwrap();
+        for key in added {
+            assert_eq!(settings.shortcuts[key].title, defaults.shortcuts[key].title);
+        }
+    }
+
+    #[test]
+    fn locked_writes_are_retried_until_released() {
+        // A sharing violation, as is_lock_error sees it on Windows
+        let locked = |e: &io::Error| is_lock_code(e.raw_os_error());
+        let attempts = Cell::new(0);
+        let result = retry_while_locked(&[Duration::from_millis(1); 4], locked, || {
+            attempts.set(attempts.get() + 1);
+            if attempts.get() < 3 {
+                Err(io::Error::from_raw_os_error(32))
+            } else {
+                Ok("saved")
+            }
+        });
+        assert_eq!(result.unwrap(), "saved");
+        assert_eq!(attempts.get(), 3);
+
+        // A lock that never goes away gives up after the last delay
+        attempts.set(0);
+        let result: io::Result<()> =
+            retry_while_locked(&[Duration::from_millis(1); 4], locked, || {
+                attempts.set(attempts.get() + 1);
+                Err(io::Error::from_raw_os_error(33))
+            });
+        assert!(locked(&result.unwrap_err()));
+        assert_eq!(attempts.get(), 5);
+
+        // Other errors aren't retried
+        attempts.set(0);
+        let result: io::Result<()> =
+            retry_while_locked(&[Duration::from_millis(1); 4], locked, || {
+                attempts.set(attempts.get() + 1);
+                Err(io::Error::from(io::ErrorKind::NotFound))
+            });
+        assert!(result.is_err());
+        assert_eq!(attempts.get(), 1);
+
+        // Read-only directories and bad ACLs aren't locks
+        assert!(!is_lock_error(&io::Error::from(
+            io::ErrorKind::PermissionDenied
+        )));
+    }
+}
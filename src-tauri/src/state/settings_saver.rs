@@ -0,0 +1,209 @@
+// state/settings_saver.rs
+// Coalesces rapid settings saves (e.g. one per keystroke in the prompt editor)
+// into at most one disk write per SAVE_DEBOUNCE, from a background thread.
+
+use crate::types::WhisperSettings;
+use log::error;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+type WriteFn = dyn Fn(&WhisperSettings) -> Result<(), String> + Send + Sync;
+
+// ============================================================================
+// SettingsSaver - managed state, created in setup() once the app dir is known
+// ============================================================================
+pub struct SettingsSaver {
+    inner: Arc<SaverInner>,
+}
+
+struct SaverInner {
+    delay: Duration,
+    write: Box<WriteFn>,
+    pending: Mutex<Pending>,
+    // Held while taking the pending settings *and* writing them, so writes
+    // land on disk in the order they were requested - the last state wins
+    write_lock: Mutex<()>,
+}
+
+#[derive(Default)]
+struct Pending {
+    settings: Option<WhisperSettings>,
+    // Set by the first save of a burst and kept by the ones after it, so
+    // non-stop typing still gets written every `delay`
+    due: Option<Instant>,
+    worker_running: bool,
+}
+
+impl SettingsSaver {
+    pub fn new<F>(delay: Duration, write: F) -> Self
+    where
+        F: Fn(&WhisperSettings) -> Result<(), String> + Send + Sync + 'static,
+    {
+        SettingsSaver {
+            inner: Arc::new(SaverInner {
+                delay,
+                write: Box::new(write),
+                pending: Mutex::new(Pending::default()),
+                write_lock: Mutex::new(()),
+            }),
+        }
+    }
+
+    // Replaces whatever is pending; it's written within `delay`
+    pub fn save(&self, settings: WhisperSettings) {
+        let mut pending = self.inner.pending.lock().unwrap();
+        pending.settings = Some(settings);
+        if pending.due.is_none() {
+            pending.due = Some(Instant::now() + self.inner.delay);
+        }
+
+        if !pending.worker_running {
+            pending.worker_running = true;
+            let inner = self.inner.clone();
+            std::thread::spawn(move || inner.run_worker());
+        }
+    }
+
+    // Writes the pending settings right away. Ok(false) if nothing was pending.
+    pub fn flush(&self) -> Result<bool, String> {
+        let _write_guard = self.inner.write_lock.lock().unwrap();
+        let settings = self.inner.pending.lock().unwrap().take();
+
+        match settings {
+            Some(settings) => (self.inner.write)(&settings).map(|_| true),
+            None => Ok(false),
+        }
+    }
+
+    // For writes that bypass the debounce (e.g. a single setting changed by a
+    // command): drops anything pending, since `write` carries newer state
+    pub fn write_through<T>(&self, write: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+        let _write_guard = self.inner.write_lock.lock().unwrap();
+        self.inner.pending.lock().unwrap().take();
+        write()
+    }
+}
+
+impl Pending {
+    // The settings to write now; the next save starts a new burst
+    fn take(&mut self) -> Option<WhisperSettings> {
+        self.due = None;
+        self.settings.take()
+    }
+}
+
+impl SaverInner {
+    fn run_worker(&self) {
+        loop {
+            let due = {
+                let mut pending = self.pending.lock().unwrap();
+                match (&pending.settings, pending.due) {
+                    (Some(_), Some(due)) => due,
+                    // Flushed or superseded in the meantime
+                    _ => {
+                        pending.due = None;
+                        pending.worker_running = false;
+                        return;
+                    }
+                }
+            };
+
+            let now = Instant::now();
+            if due > now {
+                std::thread::sleep(due - now);
+                continue;
+            }
+
+            let _write_guard = self.write_lock.lock().unwrap();
+            let settings = {
+                let mut pending = self.pending.lock().unwrap();
+                // A save may have arrived while we waited for the lock
+                if matches!(pending.due, Some(due) if due > Instant::now()) {
+                    continue;
+                }
+                pending.take()
+            };
+
+            if let Some(settings) = settings {
+                if let Err(e) = (self.write)(&settings) {
+                    error!("❌ Failed to save settings: {}", e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn counting_saver(delay: Duration) -> (SettingsSaver, Arc<AtomicUsize>, Arc<Mutex<String>>) {
+        let writes = Arc::new(AtomicUsize::new(0));
+        let last_prompt = Arc::new(Mutex::new(String::new()));
+
+        let (writes_clone, last_clone) = (writes.clone(), last_prompt.clone());
+        let saver = SettingsSaver::new(delay, move |settings| {
+            writes_clone.fetch_add(1, Ordering::SeqCst);
+            *last_clone.lock().unwrap() = settings.system_prompt.clone();
+            Ok(())
+        });
+
+        (saver, writes, last_prompt)
+    }
+
+    fn settings_with_prompt(prompt: &str) -> WhisperSettings {
+        WhisperSettings {
+            system_prompt: prompt.to_string(),
+            ..WhisperSettings::default()
+        }
+    }
+
+    #[test]
+    fn rapid_saves_coalesce_into_one_write() {
+        let (saver, writes, last_prompt) = counting_saver(Duration::from_millis(100));
+
+        let prompt = "You are a helpful assistant";
+        for end in 1..=prompt.len() {
+            saver.save(settings_with_prompt(&prompt[..end]));
+        }
+        assert_eq!(writes.load(Ordering::SeqCst), 0);
+
+        std::thread::sleep(Duration::from_millis(400));
+        assert_eq!(writes.load(Ordering::SeqCst), 1);
+        assert_eq!(*last_prompt.lock().unwrap(), prompt);
+    }
+
+    #[test]
+    fn continuous_saves_are_still_written() {
+        let (saver, writes, last_prompt) = counting_saver(SAVE_DEBOUNCE);
+
+        // Typing without a pause longer than the debounce for 2s
+        for i in 0..20 {
+            saver.save(settings_with_prompt(&format!("draft {}", i)));
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        assert!(writes.load(Ordering::SeqCst) >= 2);
+
+        std::thread::sleep(SAVE_DEBOUNCE * 2);
+        assert_eq!(*last_prompt.lock().unwrap(), "draft 19");
+    }
+
+    #[test]
+    fn flush_writes_pending_settings_immediately() {
+        let (saver, writes, last_prompt) = counting_saver(Duration::from_secs(60));
+
+        assert_eq!(saver.flush(), Ok(false));
+
+        saver.save(settings_with_prompt("first"));
+        saver.save(settings_with_prompt("second"));
+        assert_eq!(saver.flush(), Ok(true));
+        assert_eq!(writes.load(Ordering::SeqCst), 1);
+        assert_eq!(*last_prompt.lock().unwrap(), "second");
+
+        // Nothing left for the background write
+        assert_eq!(saver.flush(), Ok(false));
+    }
+}
@@ -0,0 +1,45 @@
+// state/ui_state.rs
+// Durable frontend layout state (panel sizes, last route, ...).
+// Lives in ui_state.json next to settings.json so it survives
+// delete_cache_command wiping the webview's localStorage.
+
+use super::settings::{get_app_dir, write_atomic};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+// Key -> arbitrary JSON value chosen by the frontend
+pub type UiState = HashMap<String, serde_json::Value>;
+
+fn get_ui_state_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    Ok(get_app_dir(app_handle)?.join("ui_state.json"))
+}
+
+// ============================================================================
+// Load UI state from disk (empty if the file doesn't exist yet)
+// ============================================================================
+pub fn load_ui_state(app_handle: &AppHandle) -> Result<UiState, String> {
+    let path = get_ui_state_path(app_handle)?;
+
+    if !path.exists() {
+        return Ok(UiState::new());
+    }
+
+    let contents =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read UI state file: {}", e))?;
+
+    serde_json::from_str(&contents).map_err(|e| format!("Failed to parse UI state JSON: {}", e))
+}
+
+// ============================================================================
+// Save UI state to disk
+// ============================================================================
+pub fn save_ui_state(app_handle: &AppHandle, ui_state: &UiState) -> Result<(), String> {
+    let path = get_ui_state_path(app_handle)?;
+
+    let json = serde_json::to_string_pretty(ui_state)
+        .map_err(|e| format!("Failed to serialize UI state: {}", e))?;
+
+    write_atomic(&path, &json)
+}
@@ -5,6 +5,10 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+// Bumped whenever settings.json needs more than new defaulted fields to load
+// (see migrate_settings in state/settings.rs)
+pub const SETTINGS_VERSION: u32 = 2;
+
 // ============================================================================
 // RUST CONCEPT: Derive Macros
 // ============================================================================
@@ -58,6 +62,11 @@ pub struct CustomShortcut {
 // ============================================================================
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WhisperSettings {
+    // Schema version of settings.json. Files written before this field
+    // existed are migrated as version 0 before they get here.
+    #[serde(default = "default_settings_version")]
+    pub version: u32,
+
     pub llm: String, // "chatgpt", "grok", "deepseek", "gemini", "perplexity"
     pub system_prompt: String, // Note: Rust uses snake_case instead of camelCase
     pub retry_prompt: String,
@@ -66,19 +75,213 @@ pub struct WhisperSettings {
     pub focusable: bool,
     pub show_banner: bool,
     pub opacity: f64,
+
+    // How much the opacity-up/opacity-down shortcuts change opacity
+    #[serde(default = "default_opacity_step")]
+    pub opacity_step: f64,
+
+    // "normal", "floating", "top" or "screensaver" (see set_window_level_command)
+    #[serde(default = "default_window_level")]
+    pub window_level: String,
+
+    // Off puts the window at the normal level, keeping window_level for when
+    // it's turned back on (see set_always_on_top_command)
+    #[serde(default = "default_always_on_top")]
+    pub always_on_top: bool,
+
+    // "error", "warn", "info", "debug" or "trace" (see set_log_level_command);
+    // a RUST_LOG environment variable takes precedence
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+
+    // Pixels the window moves per move-up/down/left/right shortcut; with Shift
+    // added to the shortcut it moves FAST_MOVE_MULTIPLIER times as far
+    #[serde(default = "default_move_step")]
+    pub move_step: u32,
+
+    // Whether move steps and window sizes from commands, shortcuts and
+    // screen_width/screen_height are logical pixels (scaled by the monitor's
+    // DPI) or physical ones
+    #[serde(default = "default_logical_pixels")]
+    pub logical_pixels: bool,
+
+    // How long showing and hiding the window fades it in and out; 0 turns
+    // the fade off (Windows and macOS only, where there's native opacity)
+    #[serde(default = "default_fade_duration_ms")]
+    pub fade_duration_ms: u32,
+
+    // Some window managers and fullscreen apps drop always-on-top, so while
+    // the window level is above normal it's re-applied this often; 0 = never
+    #[serde(default = "default_topmost_reassert_secs")]
+    pub topmost_reassert_secs: u32,
+
+    // Opt-in: check every provider URL is reachable when the app starts
+    #[serde(default)]
+    pub verify_providers_on_startup: bool,
+
+    // Ask before leaving a provider page whose prompt box has text in it
+    #[serde(default)]
+    pub confirm_navigation_with_input: bool,
+
+    // http(s)://[user:pass@]host[:port], None for a direct connection
+    #[serde(default)]
+    pub proxy_url: Option<String>,
+
+    // Named prompts that can be injected into the provider (run_template_command,
+    // or a shortcut whose key is "template:{name}")
+    #[serde(default)]
+    pub prompt_templates: HashMap<String, String>,
+
+    // Hold back attention-grabbing behavior (see set_dnd_command)
+    #[serde(default)]
+    pub do_not_disturb: bool,
+
+    // Process names (without .exe, case-insensitive) that count as screen
+    // recording or sharing software (see detect_screen_recording_command)
+    #[serde(default = "default_screen_recorder_apps")]
+    pub screen_recorder_apps: Vec<String>,
+
+    // Turn on content protection as soon as one of those apps is detected
+    #[serde(default)]
+    pub auto_stealth_on_recording: bool,
+
+    // Last provider conversation, reopened at startup if restore_last_session
+    #[serde(default)]
+    pub last_provider_url: Option<String>,
+
+    // Hosts of user-added providers that launch_whispr_mode_command may open,
+    // on top of the built-in PROVIDERS (e.g. "chat.mistral.ai")
+    #[serde(default)]
+    pub custom_provider_hosts: Vec<String>,
+
+    // Reopen last_provider_url at startup instead of a fresh chat
+    #[serde(default)]
+    pub restore_last_session: bool,
+
+    // Pause shortcuts that double as text editing keys (Ctrl+Left, Ctrl+A...)
+    // while a text field has focus
+    #[serde(default = "default_respect_text_input")]
+    pub respect_text_input: bool,
+
+    // Fields set by a managed deployment that the user can't change
+    // (see apply_remote_config_command)
+    #[serde(default)]
+    pub locked_fields: Vec<String>,
+
+    // Saved window spots, by slot number (see save_window_slot_command)
+    #[serde(default)]
+    pub window_slots: HashMap<u8, WindowGeometry>,
+
+    // Outer position (physical px) restored at startup
+    // (see save_window_position_command)
+    #[serde(default)]
+    pub window_x: Option<i32>,
+    #[serde(default)]
+    pub window_y: Option<i32>,
     
     // RUST CONCEPT: HashMap is like JavaScript's Map or TypeScript's Record
     // HashMap<String, ShortcutEntry> = Record<string, ShortcutEntry> in TS
     pub shortcuts: HashMap<String, ShortcutEntry>,
 }
 
+// ============================================================================
+// WindowGeometry - Outer position and size of a window (physical pixels)
+// ============================================================================
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+// ============================================================================
+// Serde Defaults
+// ============================================================================
+// Used when an older settings.json doesn't have a newer field yet.
+fn default_window_level() -> String {
+    "floating".to_string()
+}
+
+fn default_always_on_top() -> bool {
+    true
+}
+
+fn default_opacity_step() -> f64 {
+    0.1
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
+}
+
+fn default_move_step() -> u32 {
+    20
+}
+
+fn default_logical_pixels() -> bool {
+    true
+}
+
+fn default_fade_duration_ms() -> u32 {
+    150
+}
+
+fn default_topmost_reassert_secs() -> u32 {
+    5
+}
+
+fn default_settings_version() -> u32 {
+    SETTINGS_VERSION
+}
+
+fn default_respect_text_input() -> bool {
+    true
+}
+
+fn default_screen_recorder_apps() -> Vec<String> {
+    [
+        // Recorders
+        "obs",
+        "obs64",
+        "obs32",
+        "obs-studio",
+        "streamlabs obs",
+        "xsplit.core",
+        "camtasia",
+        "camtasiastudio",
+        "screenflow",
+        "snagit32",
+        "snagiteditor",
+        "bandicam",
+        "sharex",
+        "loom",
+        "simplescreenrecorder",
+        "kazam",
+        // Screen sharing (CptHost is Zoom's sharing process)
+        "zoom",
+        "zoom.us",
+        "cpthost",
+        "teams",
+        "ms-teams",
+        "discord",
+        "slack",
+        "webex",
+        "ciscowebexstart",
+    ]
+    .iter()
+    .map(|app| app.to_string())
+    .collect()
+}
+
 // ============================================================================
 // Default Settings Implementation
 // ============================================================================
 impl WhisperSettings {
     // This creates the default settings (like your defaultSettings constant)
     pub fn default() -> Self {
-        // Helper function to create default shortcuts
+        // Helper function to create default shortcuts. Entries added here
+        // reach existing settings files through migrate_settings.
         let mut shortcuts = HashMap::new();
         
         // Screenshot shortcut
@@ -240,6 +443,22 @@ impl WhisperSettings {
                 custom_shortcut: None,
             },
         );
+
+        // Next monitor shortcut
+        shortcuts.insert(
+            "next-monitor".to_string(),
+            ShortcutEntry {
+                key: "next-monitor".to_string(),
+                title: "Move to Next Monitor".to_string(),
+                description: "Move the WhisprGPT window to the next display".to_string(),
+                category: "movement".to_string(),
+                default_shortcut: PlatformShortcut {
+                    mac: "⌘ + ⌥ + N".to_string(),
+                    windows: "Ctrl + Alt + N".to_string(),
+                },
+                custom_shortcut: None,
+            },
+        );
         
         // Home shortcut
         shortcuts.insert(
@@ -257,6 +476,22 @@ impl WhisperSettings {
             },
         );
         
+        // Back shortcut
+        shortcuts.insert(
+            "back".to_string(),
+            ShortcutEntry {
+                key: "back".to_string(),
+                title: "Go Back".to_string(),
+                description: "Return to the previous provider page".to_string(),
+                category: "navigation".to_string(),
+                default_shortcut: PlatformShortcut {
+                    mac: "⌘ + Shift + B".to_string(),
+                    windows: "Ctrl + Shift + B".to_string(),
+                },
+                custom_shortcut: None,
+            },
+        );
+
         // Hide/Show shortcut
         shortcuts.insert(
             "hide-show".to_string(),
@@ -272,6 +507,70 @@ impl WhisperSettings {
                 custom_shortcut: None,
             },
         );
+
+        // Panic shortcut
+        shortcuts.insert(
+            "panic".to_string(),
+            ShortcutEntry {
+                key: "panic".to_string(),
+                title: "Panic Hide".to_string(),
+                description: "Hide the window and clear what it was showing".to_string(),
+                category: "system".to_string(),
+                default_shortcut: PlatformShortcut {
+                    mac: "⌘ + Shift + H".to_string(),
+                    windows: "Ctrl + Shift + H".to_string(),
+                },
+                custom_shortcut: None,
+            },
+        );
+
+        // Always on top shortcut
+        shortcuts.insert(
+            "always-on-top".to_string(),
+            ShortcutEntry {
+                key: "always-on-top".to_string(),
+                title: "Toggle Always On Top".to_string(),
+                description: "Keep the window above other windows, or not".to_string(),
+                category: "system".to_string(),
+                default_shortcut: PlatformShortcut {
+                    mac: "⌘ + ⌥ + P".to_string(),
+                    windows: "Ctrl + Alt + P".to_string(),
+                },
+                custom_shortcut: None,
+            },
+        );
+
+        // Opacity up shortcut
+        shortcuts.insert(
+            "opacity-up".to_string(),
+            ShortcutEntry {
+                key: "opacity-up".to_string(),
+                title: "Increase Opacity".to_string(),
+                description: "Make the WhisprGPT window less transparent".to_string(),
+                category: "display".to_string(),
+                default_shortcut: PlatformShortcut {
+                    mac: "⌘ + ⌥ + =".to_string(),
+                    windows: "Ctrl + Alt + =".to_string(),
+                },
+                custom_shortcut: None,
+            },
+        );
+
+        // Opacity down shortcut
+        shortcuts.insert(
+            "opacity-down".to_string(),
+            ShortcutEntry {
+                key: "opacity-down".to_string(),
+                title: "Decrease Opacity".to_string(),
+                description: "Make the WhisprGPT window more transparent".to_string(),
+                category: "display".to_string(),
+                default_shortcut: PlatformShortcut {
+                    mac: "⌘ + ⌥ + -".to_string(),
+                    windows: "Ctrl + Alt + -".to_string(),
+                },
+                custom_shortcut: None,
+            },
+        );
         
         // Quit shortcut
         shortcuts.insert(
@@ -293,6 +592,7 @@ impl WhisperSettings {
         // RUST CONCEPT: "Self" means WhisperSettings
         // This is like "return { ... }" in JavaScript
         Self {
+            version: SETTINGS_VERSION,
             llm: "chatgpt".to_string(),
             system_prompt: "ENTER CUSTOM PROMPT OR USE TEMPLATES".to_string(),
             retry_prompt: "ENTER RETRY/BACKUP PROMPT".to_string(),
@@ -301,6 +601,29 @@ impl WhisperSettings {
             focusable: true,
             show_banner: true,
             opacity: 1.0,
+            opacity_step: default_opacity_step(),
+            window_level: default_window_level(),
+            always_on_top: default_always_on_top(),
+            log_level: default_log_level(),
+            move_step: default_move_step(),
+            logical_pixels: default_logical_pixels(),
+            fade_duration_ms: default_fade_duration_ms(),
+            topmost_reassert_secs: default_topmost_reassert_secs(),
+            verify_providers_on_startup: false,
+            confirm_navigation_with_input: false,
+            proxy_url: None,
+            prompt_templates: HashMap::new(),
+            do_not_disturb: false,
+            screen_recorder_apps: default_screen_recorder_apps(),
+            auto_stealth_on_recording: false,
+            last_provider_url: None,
+            custom_provider_hosts: Vec::new(),
+            restore_last_session: false,
+            respect_text_input: default_respect_text_input(),
+            locked_fields: Vec::new(),
+            window_slots: HashMap::new(),
+            window_x: None,
+            window_y: None,
             shortcuts,
         }
     }
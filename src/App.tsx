@@ -12,7 +12,10 @@ import SubscribePage from "./pages/Subscription";
 import { StripeSubscription } from "@/types/types";
 import ShortcutsPage from "./pages/Shortcuts";
 import NotesPage from "./pages/Notes";
-import { listenForAuthCallback } from "./lib/tauri-auth-api";
+import {
+  listenForAuthCallback,
+  signInWithAuthCallback,
+} from "./lib/tauri-auth-api";
 import { whisprApi } from "./lib/tauri-whispr-api";
 
 // Separate component to use useNavigate hook
@@ -106,6 +109,7 @@ function AppContent() {
   useEffect(() => {
     let unsubLaunch: (() => void) | null = null;
     let unsubDashboard: (() => void) | null = null;
+    let unsubNavigate: (() => void) | null = null;
 
     console.log("🎧 Setting up Whispr mode event listeners...");
 
@@ -137,10 +141,26 @@ function AppContent() {
         console.error("❌ Failed to set up dashboard listener:", err);
       });
 
+    // Rust navigating the main window back to the dashboard while it's
+    // already showing it - routed here instead of reloading the page
+    whisprApi
+      .onNavigate((url) => {
+        console.log("🧭 Navigate event received:", url);
+        navigate("/");
+      })
+      .then((unsub) => {
+        unsubNavigate = unsub;
+        console.log("✅ Navigate listener ready");
+      })
+      .catch((err) => {
+        console.error("❌ Failed to set up navigate listener:", err);
+      });
+
     return () => {
       console.log("🧹 Cleaning up Whispr mode listeners");
       if (unsubLaunch) unsubLaunch();
       if (unsubDashboard) unsubDashboard();
+      if (unsubNavigate) unsubNavigate();
     };
   }, [navigate]);
 
@@ -154,16 +174,16 @@ function AppContent() {
       console.log("🔐 Setting up Tauri auth listener...");
 
       try {
-        unlisten = await listenForAuthCallback(async (code: string) => {
-          console.log("📥 Received auth callback with code:", code);
+        unlisten = await listenForAuthCallback(async (payload) => {
+          console.log("📥 Received auth callback:", payload.token_type);
 
           try {
-            if (!code) {
+            if (!payload.code) {
               console.error("❌ No code in callback");
               return;
             }
 
-            const { error } = await supabase.auth.exchangeCodeForSession(code);
+            const { error } = await signInWithAuthCallback(payload);
 
             if (error) {
               console.error("❌ Error exchanging code for session:", error);
@@ -3,6 +3,7 @@
 
 import { invoke } from "@tauri-apps/api/tauri";
 import { listen, UnlistenFn } from "@tauri-apps/api/event";
+import { supabase } from "./supabase/client";
 
 // ============================================================================
 // Types
@@ -15,6 +16,13 @@ export interface CheckoutResponse {
 
 export interface AuthCallbackPayload {
   code: string;
+  token_type: "code" | "access_token";
+  refresh_token: string | null;
+}
+
+export interface AuthErrorPayload {
+  error: string;
+  description: string | null;
 }
 
 // ============================================================================
@@ -35,21 +43,18 @@ export async function openExternalUrl(url: string): Promise<void> {
 // Open Stripe Checkout Portal
 // ============================================================================
 // Replaces: window.auth.openCheckoutPortal()
+// Throws if the checkout URL couldn't be opened.
 
 export async function openCheckoutPortal(
   userId: string
 ): Promise<CheckoutResponse> {
   try {
-    const response = await invoke<CheckoutResponse>("open_checkout_portal", {
+    return await invoke<CheckoutResponse>("open_checkout_portal", {
       userId,
     });
-    return response;
   } catch (error) {
     console.error("Failed to open checkout portal:", error);
-    return {
-      success: false,
-      error: error instanceof Error ? error.message : "Unknown error",
-    };
+    throw error;
   }
 }
 
@@ -57,20 +62,64 @@ export async function openCheckoutPortal(
 // Listen for Auth Callback (OAuth redirect)
 // ============================================================================
 // Replaces: window.auth.authCallback(callback)
+// onError gets sign-ins that were cancelled, refused by the provider, or
+// rejected by the app (e.g. a link from a sign-in this app didn't start).
 
 export async function listenForAuthCallback(
-  callback: (code: string) => void
+  callback: (payload: AuthCallbackPayload) => void,
+  onError?: (error: AuthErrorPayload) => void
 ): Promise<UnlistenFn> {
-  const unlisten = await listen<AuthCallbackPayload>(
+  const unlistenCallback = await listen<AuthCallbackPayload>(
     "auth-callback",
     (event) => {
       console.log("🔐 Received auth callback:", event.payload);
-      callback(event.payload.code);
+      callback(event.payload);
+    }
+  );
+  const unlistenError = await listen<AuthErrorPayload>(
+    "auth-error",
+    (event) => {
+      console.error("❌ Sign-in failed:", event.payload);
+      onError?.(event.payload);
     }
   );
 
   console.log("👂 Listening for auth callbacks...");
-  return unlisten;
+  await frontendReady();
+  return () => {
+    unlistenCallback();
+    unlistenError();
+  };
+}
+
+// ============================================================================
+// Sign In From Auth Callback
+// ============================================================================
+// A PKCE code is exchanged for a session; an implicit-flow access token
+// already is one.
+
+export async function signInWithAuthCallback(payload: AuthCallbackPayload) {
+  if (payload.token_type === "access_token") {
+    return supabase.auth.setSession({
+      access_token: payload.code,
+      refresh_token: payload.refresh_token ?? "",
+    });
+  }
+  return supabase.auth.exchangeCodeForSession(payload.code);
+}
+
+// ============================================================================
+// Frontend Ready
+// ============================================================================
+// Call once the auth listeners are registered: auth links that arrived
+// earlier (e.g. the one the app was launched with) are delivered then.
+
+export async function frontendReady(): Promise<void> {
+  try {
+    await invoke("frontend_ready_command");
+  } catch (error) {
+    console.error("Failed to signal frontend readiness:", error);
+  }
 }
 
 // ============================================================================
@@ -81,9 +130,9 @@ export async function listenForAuthCallback(
 //    await openExternalUrl(data.url);
 //
 // 2. Listen for OAuth callback (App.tsx):
-//    const unlisten = await listenForAuthCallback((code) => {
-//      console.log('Got auth code:', code);
-//      // Exchange code for session
+//    const unlisten = await listenForAuthCallback(async (payload) => {
+//      console.log('Got auth callback:', payload.token_type);
+//      const { error } = await signInWithAuthCallback(payload);
 //    });
 //    // Later: unlisten();
 //
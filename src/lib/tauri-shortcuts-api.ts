@@ -2,19 +2,31 @@
 // Tauri Shortcuts API - Replaces Electron shortcuts functionality
 
 import { invoke } from "@tauri-apps/api/tauri";
+import { once } from "@tauri-apps/api/event";
 import type { WhisperSettings } from "@/types/types";
 
 // ============================================================================
 // Register All Shortcuts
 // ============================================================================
 // Replaces: Electron's globalShortcut.register() in shortcutEvents.ts
+// A shortcut the OS refuses doesn't stop the others; it's listed in `failed`.
 
-export async function registerShortcuts(): Promise<boolean> {
+export interface RegistrationResult {
+  // Command keys whose accelerators all registered
+  registered: string[];
+  // [command key, error]
+  failed: [string, string][];
+}
+
+export async function registerShortcuts(): Promise<RegistrationResult> {
   try {
     console.log("📋 Registering all global shortcuts...");
-    const success = await invoke<boolean>("register_shortcuts_command");
-    console.log("✅ Shortcuts registered:", success);
-    return success;
+    const result = await invoke<RegistrationResult>("register_shortcuts_command");
+    if (result.failed.length > 0) {
+      console.warn("⚠️ Some shortcuts didn't register:", result.failed);
+    }
+    console.log("✅ Shortcuts registered:", result.registered.length);
+    return result;
   } catch (error) {
     console.error("❌ Failed to register shortcuts:", error);
     throw error;
@@ -38,6 +50,40 @@ export async function unregisterShortcuts(): Promise<boolean> {
   }
 }
 
+// ============================================================================
+// Registered Shortcuts
+// ============================================================================
+// What the last registration actually got from the OS (registered: false
+// means the OS or another app holds that accelerator)
+
+export interface RegisteredShortcut {
+  command_key: string;
+  accelerator: string;
+  registered: boolean;
+  // Set for accelerators the OS reserves or uses itself
+  warning: string | null;
+  // Why it wasn't registered
+  error: string | null;
+}
+
+export async function getRegisteredShortcuts(): Promise<RegisteredShortcut[]> {
+  return await invoke<RegisteredShortcut[]>("get_registered_shortcuts_command");
+}
+
+// ============================================================================
+// Record Shortcut
+// ============================================================================
+// Resolves with the next key combination pressed in this window (normalized,
+// e.g. "Ctrl+Shift+K"), or null on timeout or keys that can't be a shortcut
+
+export async function recordShortcut(): Promise<string | null> {
+  const recorded = new Promise<string | null>((resolve) => {
+    once<string | null>("shortcut-recorded", (event) => resolve(event.payload));
+  });
+  await invoke("start_shortcut_recording_command");
+  return await recorded;
+}
+
 // ============================================================================
 // Update Single Shortcut
 // ============================================================================
@@ -87,12 +133,13 @@ export async function resetShortcut(commandKey: string): Promise<boolean> {
 // ============================================================================
 // Replaces: window.electron.refreshShortcuts()
 
-export async function refreshShortcuts(): Promise<void> {
+export async function refreshShortcuts(): Promise<RegistrationResult> {
   try {
     console.log("🔄 Refreshing all shortcuts...");
     await unregisterShortcuts();
-    await registerShortcuts();
+    const result = await registerShortcuts();
     console.log("✅ Shortcuts refreshed");
+    return result;
   } catch (error) {
     console.error("❌ Failed to refresh shortcuts:", error);
     throw error;
@@ -8,6 +8,11 @@ export interface WhisprModePayload {
   url: string;
 }
 
+export interface NavigatePayload {
+  id: number;
+  url: string;
+}
+
 // ============================================================================
 // Whispr Mode API
 // ============================================================================
@@ -28,6 +33,13 @@ export const whisprApi = {
     await invoke("navigate_to_dashboard_command");
   },
 
+  /**
+   * Full URL the main window is showing (a provider page or the dashboard)
+   */
+  async getCurrentRoute(): Promise<string> {
+    return invoke<string>("get_current_route_command");
+  },
+
   /**
    * Listen for Whispr mode launch events
    * Call this in your App.tsx to handle navigation
@@ -40,6 +52,17 @@ export const whisprApi = {
     });
   },
 
+  /**
+   * Handle main window navigation to the dashboard in the router
+   * Unacknowledged events fall back to a full page load after a second
+   */
+  onNavigate(callback: (url: string) => void): Promise<() => void> {
+    return listen<NavigatePayload>("navigate", async (event) => {
+      await invoke("ack_navigation_command", { id: event.payload.id });
+      callback(event.payload.url);
+    });
+  },
+
   /**
    * Listen for dashboard navigation events
    * Triggered by Ctrl/Cmd+B hotkey
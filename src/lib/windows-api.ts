@@ -3,6 +3,7 @@
 // This replaces window.electron.* calls for window operations
 
 import { invoke } from "@tauri-apps/api/tauri";
+import { listen } from "@tauri-apps/api/event";
 
 // ============================================================================
 // Window Management API
@@ -36,6 +37,18 @@ export const windowApi = {
     await invoke("set_opacity_command", { opacity });
   },
 
+  /**
+   * Raise or lower the saved opacity by the opacity_step setting
+   * @returns The new opacity (apply it with CSS on Linux)
+   */
+  async increaseOpacity(): Promise<number> {
+    return await invoke("increase_opacity_command");
+  },
+
+  async decreaseOpacity(): Promise<number> {
+    return await invoke("decrease_opacity_command");
+  },
+
   /**
    * Move window by delta
    * @param deltaX - Horizontal movement in pixels
@@ -77,6 +90,13 @@ export const windowApi = {
     await invoke("move_window_command", { deltaX: amount, deltaY: 0 });
   },
 
+  /**
+   * Move the window to the same spot on the next monitor (wraps around)
+   */
+  async moveToNextMonitor(): Promise<void> {
+    await invoke("move_to_next_monitor_command");
+  },
+
   /**
    * Hide the window
    */
@@ -84,9 +104,32 @@ export const windowApi = {
     await invoke("hide_window_command");
   },
 
+  /**
+   * Hide the window and clear what it was showing (see onClearContent)
+   */
+  async panicHide(): Promise<void> {
+    await invoke("panic_hide_command");
+  },
+
+  /**
+   * Bring the window back after a panic hide
+   */
+  async panicRestore(): Promise<void> {
+    await invoke("panic_restore_command");
+  },
+
+  /**
+   * Listen for panic hides (button or shortcut); wipe any on-screen content
+   */
+  onClearContent(callback: () => void): Promise<() => void> {
+    return listen("clear-content", () => {
+      callback();
+    });
+  },
+
   /**
    * Show the window
-   * @param opacity - Opacity to restore (default: 1.0)
+   * @param opacity - Opacity to show it at, on Windows/macOS (default: 1.0)
    */
   async show(opacity: number = 1.0): Promise<void> {
     await invoke("show_window_command", { opacity });
@@ -94,20 +137,36 @@ export const windowApi = {
 
   /**
    * Toggle window visibility
-   * @param opacity - Opacity when showing (default: 1.0)
+   * @param opacity - Opacity to show it at, on Windows/macOS (default: 1.0)
    */
   async toggleVisibility(opacity: number = 1.0): Promise<void> {
     await invoke("toggle_window_visibility_command", { opacity });
   },
 
   /**
-   * Set always on top
+   * Set always on top (saved in the settings)
    * @param alwaysOnTop - Whether window should stay on top
    */
   async setAlwaysOnTop(alwaysOnTop: boolean): Promise<void> {
     await invoke("set_always_on_top_command", { alwaysOnTop });
   },
 
+  /**
+   * Flip always-on-top (saved in the settings)
+   * @returns Whether it's now on
+   */
+  async toggleAlwaysOnTop(): Promise<boolean> {
+    return await invoke("toggle_always_on_top_command");
+  },
+
+  /**
+   * Re-apply always-on-top now (it's also re-applied every few seconds)
+   * @returns Whether it had been lost (always false on Linux)
+   */
+  async reassertAlwaysOnTop(): Promise<boolean> {
+    return await invoke("reassert_always_on_top_command");
+  },
+
   /**
    * Get app version
    * Replaces: window.electron.getAppVersion()
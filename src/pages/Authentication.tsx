@@ -2,6 +2,7 @@ import { useEffect } from 'react'
 import { invoke } from '@tauri-apps/api/tauri'
 import { listen } from '@tauri-apps/api/event'
 import { supabase } from '../lib/supabase/client'
+import { frontendReady, signInWithAuthCallback } from '../lib/tauri-auth-api'
 
 export default function Auth() {
   // Set up listener for deep link callbacks
@@ -14,10 +15,10 @@ export default function Auth() {
         const code = event.payload.code
         
         if (code) {
-          console.log('🔄 Exchanging code for session...')
+          console.log('🔄 Signing in with', event.payload.token_type)
           
           try {
-            const { data, error } = await supabase.auth.exchangeCodeForSession(code)
+            const { data, error } = await signInWithAuthCallback(event.payload)
             
             if (error) {
               console.error('❌ Failed to exchange code:', error)
@@ -34,8 +35,20 @@ export default function Auth() {
           }
         }
       })
-      
-      return unlisten
+
+      // Cancelled, refused by the provider, or rejected by the app
+      const unlistenError = await listen('auth-error', (event: any) => {
+        console.error('❌ Sign-in failed:', event.payload)
+        alert('Login failed: ' + (event.payload.description || event.payload.error))
+      })
+
+      // Auth links that came in before these listeners existed
+      await frontendReady()
+
+      return () => {
+        unlisten()
+        unlistenError()
+      }
     }
     
     const unlistenPromise = setupListener()